@@ -0,0 +1,8 @@
+#!/bin/sh
+# Run the C API tests under AddressSanitizer (needs a nightly toolchain).
+# Usage: ./capi/asan_test.sh [target-triple]
+set -e
+TARGET=${1:-x86_64-unknown-linux-gnu}
+cd "$(dirname "$0")/.."
+RUSTFLAGS="-Zsanitizer=address" RUSTDOCFLAGS="-Zsanitizer=address" \
+  cargo +nightly test -Zbuild-std --target "$TARGET" -p opencc_jieba_capi
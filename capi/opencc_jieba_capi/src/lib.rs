@@ -7,6 +7,8 @@ pub extern "C" fn opencc_new() -> *mut OpenCC {
     Box::into_raw(Box::new(OpenCC::new()))
 }
 
+// Safety: instance must be null or a pointer returned by opencc_new, freed once
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn opencc_free(instance: *mut OpenCC) {
     if !instance.is_null() {
@@ -17,6 +19,8 @@ pub extern "C" fn opencc_free(instance: *mut OpenCC) {
     }
 }
 
+// Safety: instance from opencc_new; input and config must be valid NUL-terminated strings
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn opencc_convert(
     instance: *const OpenCC,
@@ -42,6 +46,8 @@ pub extern "C" fn opencc_convert(
     c_result.into_raw()
 }
 
+// Safety: ptr must be null or a string returned by this library, freed once
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn opencc_string_free(ptr: *mut std::os::raw::c_char) {
     if !ptr.is_null() {
@@ -52,6 +58,8 @@ pub extern "C" fn opencc_string_free(ptr: *mut std::os::raw::c_char) {
     }
 }
 
+// Safety: instance from opencc_new; input must be a valid NUL-terminated UTF-8 string
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn opencc_jieba_cut(
     instance: *const OpenCC,
@@ -77,17 +85,20 @@ pub extern "C" fn opencc_jieba_cut(
 
     result_ptrs.push(ptr::null_mut());
 
-    let result_ptr = result_ptrs.as_mut_ptr();
-    std::mem::forget(result_ptrs);
-
-    result_ptr
+    // Boxed slice: capacity == len, so opencc_free_string_array can rebuild it exactly
+    Box::into_raw(result_ptrs.into_boxed_slice()) as *mut *mut c_char
 }
 
+// Safety: array must be null or returned by opencc_jieba_cut, freed once
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn opencc_free_string_array(array: *mut *mut c_char) {
+    if array.is_null() {
+        return;
+    }
     let mut i = 0;
     loop {
-        let ptr = unsafe { *array.offset(i) };
+        let ptr = unsafe { *array.add(i) };
         if ptr.is_null() {
             break;
         }
@@ -96,8 +107,14 @@ pub extern "C" fn opencc_free_string_array(array: *mut *mut c_char) {
         }
         i += 1;
     }
+    // Free the array itself with the same allocator (len + 1 for the null terminator)
+    unsafe {
+        let _ = Box::from_raw(ptr::slice_from_raw_parts_mut(array, i + 1));
+    }
 }
 
+// Safety: strings must be a null-terminated array of valid C strings; delimiter non-null
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn join_str(strings: *mut *mut c_char, delimiter: *const c_char) -> *mut c_char {
     // Ensure delimiter is not null
@@ -161,6 +178,8 @@ pub extern "C" fn opencc_jieba_cut_and_join(
     joined_ptr
 }
 
+// Safety: instance from opencc_new; input must be a valid NUL-terminated string
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 #[no_mangle]
 pub extern "C" fn opencc_zho_check(
     instance: *const OpenCC,
@@ -272,7 +291,7 @@ mod tests {
             if ptr.is_null() {
                 break;
             }
-            let c_str = unsafe { CString::from_raw(ptr) };
+            let c_str = unsafe { CStr::from_ptr(ptr) };
             let string = c_str.to_str().unwrap().to_owned();
             result_strings.push(string);
             i += 1;
@@ -285,8 +304,8 @@ mod tests {
         assert_eq!(result_strings, expected);
 
         // Free memory
+        opencc_free_string_array(result);
         unsafe {
-            // opencc_free_string_array(result);
             let _ = CString::from_raw(input);
         }
     }
@@ -318,7 +337,7 @@ mod tests {
 
     #[test]
     fn test_join_str() {
-        let strings = vec![
+        let strings = [
             CString::new("Hello").unwrap().into_raw(),
             CString::new("World").unwrap().into_raw(),
             ptr::null_mut(), // Add null pointer to the end of the array
@@ -329,5 +348,16 @@ mod tests {
         assert!(!result.is_null());
         let result_string = unsafe { CString::from_raw(result).into_string().unwrap() };
         assert_eq!(result_string, "Hello World");
+        unsafe {
+            let _ = CString::from_raw(strings[0]);
+            let _ = CString::from_raw(strings[1]);
+            let _ = CString::from_raw(delimiter);
+        }
+    }
+
+    #[test]
+    fn test_opencc_free_string_array_null() {
+        // Must be a no-op, like opencc_free / opencc_string_free
+        opencc_free_string_array(ptr::null_mut());
     }
 }
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use arrow_array::{Array, ArrayRef, GenericStringArray, OffsetSizeTrait, RecordBatch};
+use arrow_schema::{ArrowError, DataType};
+
+use crate::OpenCC;
+
+impl OpenCC {
+    // Nulls are kept as nulls
+    pub fn convert_string_array<O: OffsetSizeTrait>(
+        &self,
+        array: &GenericStringArray<O>,
+        config: &str,
+        punctuation: bool,
+    ) -> GenericStringArray<O> {
+        array
+            .iter()
+            .map(|value| value.map(|text| self.convert(text, config, punctuation)))
+            .collect()
+    }
+
+    // Convert one Utf8 / LargeUtf8 column, other columns are passed through
+    pub fn convert_record_batch(
+        &self,
+        batch: &RecordBatch,
+        column: &str,
+        config: &str,
+        punctuation: bool,
+    ) -> Result<RecordBatch, ArrowError> {
+        let index = batch.schema().index_of(column)?;
+        let source = batch.column(index);
+        let converted: ArrayRef = match source.data_type() {
+            DataType::Utf8 => Arc::new(
+                self.convert_string_array(
+                    source
+                        .as_any()
+                        .downcast_ref::<GenericStringArray<i32>>()
+                        .unwrap(),
+                    config,
+                    punctuation,
+                ),
+            ),
+            DataType::LargeUtf8 => Arc::new(
+                self.convert_string_array(
+                    source
+                        .as_any()
+                        .downcast_ref::<GenericStringArray<i64>>()
+                        .unwrap(),
+                    config,
+                    punctuation,
+                ),
+            ),
+            data_type => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "column {} is {}, expected Utf8 or LargeUtf8",
+                    column, data_type
+                )))
+            }
+        };
+
+        let mut columns = batch.columns().to_vec();
+        columns[index] = converted;
+        RecordBatch::try_new(batch.schema(), columns)
+    }
+}
@@ -1,59 +1,133 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::Write;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 use std::{fs, io};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    // Overlay entries replace existing ones
+    Override,
+    // Existing entries win, overlay only adds new keys
+    Keep,
+    // Fail if a key exists with a different value
+    ErrorOnConflict,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReversedTable {
+    pub table: HashMap<String, String>,
+    // Values shared by several keys: (value, all keys mapping to it, sorted)
+    pub ambiguous: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableDiff {
+    pub table: &'static str,
+    // (key, value), sorted by key
+    pub added: Vec<(String, String)>,
+    pub removed: Vec<(String, String)>,
+    // (key, old value, new value)
+    pub changed: Vec<(String, String, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableStats {
+    pub table: &'static str,
+    pub entries: usize,
+    // Key length in chars -> number of keys
+    pub key_len_histogram: BTreeMap<usize, usize>,
+    // Approximate: string buffers plus hash table buckets (1 control byte each)
+    pub heap_bytes: usize,
+}
+
+// Keys in sorted order so generated dictionary.json is byte-reproducible
+fn serialize_sorted<S: Serializer>(
+    map: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+fn serialize_alternatives_sorted<S: Serializer>(
+    alternatives: &HashMap<String, HashMap<String, Vec<String>>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    alternatives
+        .iter()
+        .map(|(table, map)| (table, map.iter().collect::<BTreeMap<_, _>>()))
+        .collect::<BTreeMap<_, _>>()
+        .serialize(serializer)
+}
+
+// OpenCC text dictionary file names, in the same order as Dictionary::tables()
+const TABLE_FILES: [&str; 16] = [
+    "STCharacters.txt",
+    "STPhrases.txt",
+    "TSCharacters.txt",
+    "TSPhrases.txt",
+    "TWPhrases.txt",
+    "TWPhrasesRev.txt",
+    "TWVariants.txt",
+    "TWVariantsRev.txt",
+    "TWVariantsRevPhrases.txt",
+    "HKVariants.txt",
+    "HKVariantsRev.txt",
+    "HKVariantsRevPhrases.txt",
+    "JPShinjitaiCharacters.txt",
+    "JPShinjitaiPhrases.txt",
+    "JPVariants.txt",
+    "JPVariantsRev.txt",
+];
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
 pub struct Dictionary {
+    #[serde(serialize_with = "serialize_sorted")]
     pub st_characters: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub st_phrases: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub ts_characters: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub ts_phrases: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub tw_phrases: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub tw_phrases_rev: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub tw_variants: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub tw_variants_rev: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub tw_variants_rev_phrases: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub hk_variants: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub hk_variants_rev: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub hk_variants_rev_phrases: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub jps_characters: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub jps_phrases: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub jp_variants: HashMap<String, String>,
+    #[serde(serialize_with = "serialize_sorted")]
     pub jp_variants_rev: HashMap<String, String>,
-}
-
-impl Default for Dictionary {
-    fn default() -> Self {
-        Dictionary {
-            st_characters: HashMap::new(),
-            st_phrases: HashMap::new(),
-            ts_characters: HashMap::new(),
-            ts_phrases: HashMap::new(),
-            tw_phrases: HashMap::new(),
-            tw_phrases_rev: HashMap::new(),
-            tw_variants: HashMap::new(),
-            tw_variants_rev: HashMap::new(),
-            tw_variants_rev_phrases: HashMap::new(),
-            hk_variants: HashMap::new(),
-            hk_variants_rev: HashMap::new(),
-            hk_variants_rev_phrases: HashMap::new(),
-            jps_characters: HashMap::new(),
-            jps_phrases: HashMap::new(),
-            jp_variants: HashMap::new(),
-            jp_variants_rev: HashMap::new(),
-        }
-    }
+    // Table name -> keys with more than one candidate in the source text file.
+    // Filled by from_path() and with_alternatives(); patch/merge drop stale entries.
+    // Optional in JSON so dictionaries saved without it still load.
+    #[serde(default, serialize_with = "serialize_alternatives_sorted")]
+    pub alternatives: HashMap<String, HashMap<String, Vec<String>>>,
 }
 
 impl Dictionary {
     pub fn new() -> Self {
         let json_data = include_str!("dicts/dictionary.json");
-        serde_json::from_str(&json_data).unwrap_or_else(|_| {
+        serde_json::from_str(json_data).unwrap_or_else(|_| {
             eprintln!("Error: Failed to deserialize JSON data.");
             Dictionary::default()
         })
@@ -110,8 +184,53 @@ impl Dictionary {
             jps_phrases,
             jp_variants,
             jp_variants_rev,
+            ..Default::default()
         }
     }
+
+    // Load the OpenCC text dictionaries (STCharacters.txt, ...) from a directory,
+    // alternatives included
+    pub fn from_path<P: AsRef<Path>>(dir: P) -> io::Result<Self> {
+        let dir = dir.as_ref();
+        let mut dictionary = Dictionary::default();
+        let names = dictionary.tables().map(|(name, _)| name);
+
+        for (name, filename) in names.into_iter().zip(TABLE_FILES) {
+            let file = File::open(dir.join(filename))?;
+            let candidates = Dictionary::load_candidates_from_reader(BufReader::new(file))?;
+            dictionary.set_table(name, candidates);
+        }
+        Ok(dictionary)
+    }
+
+    // First candidate goes into the table, the full list into alternatives if there are more
+    fn set_table(&mut self, name: &'static str, candidates: HashMap<String, Vec<String>>) {
+        let mut table = HashMap::with_capacity(candidates.len());
+        let mut alternatives = HashMap::new();
+        for (phrase, values) in candidates {
+            table.insert(phrase.clone(), values[0].clone());
+            if values.len() > 1 {
+                alternatives.insert(phrase, values);
+            }
+        }
+        *self.table_mut(name).unwrap() = table;
+        if alternatives.is_empty() {
+            self.alternatives.remove(name);
+        } else {
+            self.alternatives.insert(name.to_string(), alternatives);
+        }
+    }
+
+    // Directory -> from_path, file -> from_json_file
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        if path.is_dir() {
+            Dictionary::from_path(path)
+        } else {
+            Dictionary::from_json_file(&path.to_string_lossy())
+        }
+    }
+
     #[allow(dead_code)]
     pub fn from_json_file(filename: &str) -> io::Result<Self> {
         // Read the contents of the JSON file
@@ -122,46 +241,404 @@ impl Dictionary {
         Ok(dictionary)
     }
 
-    #[allow(dead_code)]
-    fn load_dictionary_from_path<P>(filename: P) -> io::Result<HashMap<String, String>>
-    where
-        P: AsRef<Path>,
-    {
-        let file = File::open(filename)?;
-        let mut dictionary = HashMap::new();
+    pub fn tables(&self) -> [(&'static str, &HashMap<String, String>); 16] {
+        [
+            ("st_characters", &self.st_characters),
+            ("st_phrases", &self.st_phrases),
+            ("ts_characters", &self.ts_characters),
+            ("ts_phrases", &self.ts_phrases),
+            ("tw_phrases", &self.tw_phrases),
+            ("tw_phrases_rev", &self.tw_phrases_rev),
+            ("tw_variants", &self.tw_variants),
+            ("tw_variants_rev", &self.tw_variants_rev),
+            ("tw_variants_rev_phrases", &self.tw_variants_rev_phrases),
+            ("hk_variants", &self.hk_variants),
+            ("hk_variants_rev", &self.hk_variants_rev),
+            ("hk_variants_rev_phrases", &self.hk_variants_rev_phrases),
+            ("jps_characters", &self.jps_characters),
+            ("jps_phrases", &self.jps_phrases),
+            ("jp_variants", &self.jp_variants),
+            ("jp_variants_rev", &self.jp_variants_rev),
+        ]
+    }
+
+    // Write each table as an OpenCC text dictionary (same file names as from_path),
+    // alternatives space-separated after the current value
+    pub fn export_to_dir<P: AsRef<Path>>(&self, dir: P) -> io::Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        for ((name, table), filename) in self.tables().iter().zip(TABLE_FILES) {
+            let mut phrases: Vec<&String> = table.keys().collect();
+            phrases.sort();
+            let mut writer = io::BufWriter::new(File::create(dir.join(filename))?);
+            for phrase in phrases {
+                writeln!(
+                    writer,
+                    "{}\t{}",
+                    phrase,
+                    self.get_all(name, phrase).join(" ")
+                )?;
+            }
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn tables_mut(&mut self) -> [&mut HashMap<String, String>; 16] {
+        [
+            &mut self.st_characters,
+            &mut self.st_phrases,
+            &mut self.ts_characters,
+            &mut self.ts_phrases,
+            &mut self.tw_phrases,
+            &mut self.tw_phrases_rev,
+            &mut self.tw_variants,
+            &mut self.tw_variants_rev,
+            &mut self.tw_variants_rev_phrases,
+            &mut self.hk_variants,
+            &mut self.hk_variants_rev,
+            &mut self.hk_variants_rev_phrases,
+            &mut self.jps_characters,
+            &mut self.jps_phrases,
+            &mut self.jp_variants,
+            &mut self.jp_variants_rev,
+        ]
+    }
+
+    pub(crate) fn table(&self, name: &str) -> Option<&HashMap<String, String>> {
+        self.tables()
+            .into_iter()
+            .find(|(table, _)| *table == name)
+            .map(|(_, dictionary)| dictionary)
+    }
+
+    fn table_mut(&mut self, name: &str) -> Option<&mut HashMap<String, String>> {
+        let index = self.tables().iter().position(|(table, _)| *table == name)?;
+        self.tables_mut().into_iter().nth(index)
+    }
+
+    // Patch file: "[table_name]" section headers (field names, e.g. [st_phrases]),
+    // then "+key<TAB>value" to add/replace or "-key" to remove; '#' starts a comment.
+    // The whole patch is validated before anything is applied.
+    pub fn apply_patch<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        let invalid = |line_no: usize, message: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("patch line {}: {}", line_no, message),
+            )
+        };
+        let mut operations: Vec<(&'static str, String, Option<String>)> = Vec::new();
+        let mut section: Option<&'static str> = None;
 
-        for line in BufReader::new(file).lines() {
+        for (index, line) in reader.lines().enumerate() {
             let line = line?;
-            // let parts: Vec<&str> = line.split('\t').collect();
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() > 1 {
-                let phrase = parts[0].to_string();
-                let translation = parts[1].to_string();
-                dictionary.insert(phrase, translation);
+            let line_no = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let table = self.tables().into_iter().find(|(table, _)| *table == name);
+                section = Some(table.ok_or_else(|| invalid(line_no, "unknown table"))?.0);
+                continue;
+            }
+            let table = section.ok_or_else(|| invalid(line_no, "entry before [table]"))?;
+            if let Some(entry) = line.strip_prefix('+') {
+                // Split on the first tab only, so a value may contain spaces
+                let (key, value) = entry
+                    .split_once('\t')
+                    .map(|(key, value)| (key.trim(), value.trim()))
+                    .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+                    .ok_or_else(|| invalid(line_no, "expected +key<TAB>value"))?;
+                operations.push((table, key.to_string(), Some(value.to_string())));
+            } else if let Some(key) = line.strip_prefix('-') {
+                let key = key.trim();
+                if key.is_empty() {
+                    return Err(invalid(line_no, "expected -key"));
+                }
+                operations.push((table, key.to_string(), None));
             } else {
-                eprintln!("Invalid line format: {}", line);
+                return Err(invalid(line_no, "expected '+' or '-'"));
             }
         }
 
-        Ok(dictionary)
+        for (table, key, value) in operations {
+            if let Some(alternatives) = self.alternatives.get_mut(table) {
+                alternatives.remove(&key);
+            }
+            let dictionary = self.table_mut(table).unwrap();
+            match value {
+                Some(value) => dictionary.insert(key, value),
+                None => dictionary.remove(&key),
+            };
+        }
+        Ok(())
     }
 
-    #[allow(dead_code)]
-    fn load_dictionary_from_str(dictionary_content: &str) -> io::Result<HashMap<String, String>> {
-        let mut dictionary = HashMap::new();
-
-        for line in dictionary_content.lines() {
-            let parts: Vec<&str> = line.split_whitespace().collect();
-            if parts.len() >= 2 {
-                let phrase = parts[0].to_string();
-                let translation = parts[1].to_string();
-                dictionary.insert(phrase, translation);
+    pub fn apply_patch_from_path<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.apply_patch(BufReader::new(File::open(path)?))
+    }
+
+    // Layer another dictionary (e.g. a company glossary) over this one, table by table.
+    // With ErrorOnConflict nothing is merged if any conflict is found.
+    pub fn merge(&mut self, overlay: &Dictionary, strategy: MergeStrategy) -> io::Result<()> {
+        if strategy == MergeStrategy::ErrorOnConflict {
+            for ((name, base), (_, over)) in self.tables().iter().zip(overlay.tables()) {
+                for (key, value) in over {
+                    if let Some(existing) = base.get(key) {
+                        if existing != value {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!(
+                                    "merge conflict in {}: {} -> {} (existing {})",
+                                    name, key, value, existing
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        let names = self.tables().map(|(name, _)| name);
+        for (name, (_, over)) in names.into_iter().zip(overlay.tables()) {
+            let over_alternatives = overlay.alternatives.get(name);
+            for (key, value) in over {
+                let base = self.table_mut(name).unwrap();
+                if strategy == MergeStrategy::Keep && base.contains_key(key) {
+                    continue;
+                }
+                base.insert(key.clone(), value.clone());
+                // The overlay's candidates replace ours for every key it sets
+                match over_alternatives.and_then(|alternatives| alternatives.get(key)) {
+                    Some(candidates) => {
+                        self.alternatives
+                            .entry(name.to_string())
+                            .or_default()
+                            .insert(key.clone(), candidates.clone());
+                    }
+                    None => {
+                        if let Some(alternatives) = self.alternatives.get_mut(name) {
+                            alternatives.remove(key);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn stats(&self) -> Vec<TableStats> {
+        self.tables()
+            .into_iter()
+            .map(|(table, dictionary)| {
+                let mut key_len_histogram = BTreeMap::new();
+                let mut heap_bytes =
+                    dictionary.capacity() * (std::mem::size_of::<(String, String)>() + 1);
+                for (key, value) in dictionary {
+                    *key_len_histogram.entry(key.chars().count()).or_insert(0) += 1;
+                    heap_bytes += key.capacity() + value.capacity();
+                }
+                TableStats {
+                    table,
+                    entries: dictionary.len(),
+                    key_len_histogram,
+                    heap_bytes,
+                }
+            })
+            .collect()
+    }
+
+    // Entries added / removed / changed from self to other, only tables that differ
+    pub fn diff(&self, other: &Dictionary) -> Vec<TableDiff> {
+        self.tables()
+            .iter()
+            .zip(other.tables())
+            .filter_map(|((table, old), (_, new))| {
+                let mut added: Vec<(String, String)> = new
+                    .iter()
+                    .filter(|(key, _)| !old.contains_key(*key))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+                let mut removed: Vec<(String, String)> = old
+                    .iter()
+                    .filter(|(key, _)| !new.contains_key(*key))
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect();
+                let mut changed: Vec<(String, String, String)> = old
+                    .iter()
+                    .filter_map(|(key, old_value)| match new.get(key) {
+                        Some(new_value) if new_value != old_value => {
+                            Some((key.clone(), old_value.clone(), new_value.clone()))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                if added.is_empty() && removed.is_empty() && changed.is_empty() {
+                    return None;
+                }
+                added.sort();
+                removed.sort();
+                changed.sort();
+                Some(TableDiff {
+                    table,
+                    added,
+                    removed,
+                    changed,
+                })
+            })
+            .collect()
+    }
+
+    // Inverse mapping of a table (e.g. tw_phrases -> tw_phrases_rev).
+    // Many-to-one values are reported; the identity key wins, else the smallest key.
+    pub fn reversed(table: &HashMap<String, String>) -> ReversedTable {
+        let mut sources: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (key, value) in table {
+            sources.entry(value).or_default().push(key);
+        }
+
+        let mut reversed = HashMap::with_capacity(sources.len());
+        let mut ambiguous = Vec::new();
+        for (value, mut keys) in sources {
+            keys.sort_unstable();
+            let chosen = if keys.contains(&value) {
+                value
             } else {
-                eprintln!("Invalid line format: {}", line);
+                keys[0]
+            };
+            reversed.insert(value.to_string(), chosen.to_string());
+            if keys.len() > 1 {
+                ambiguous.push((
+                    value.to_string(),
+                    keys.into_iter().map(String::from).collect(),
+                ));
             }
         }
+        ambiguous.sort();
 
-        Ok(dictionary)
+        ReversedTable {
+            table: reversed,
+            ambiguous,
+        }
+    }
+
+    // Load multi-candidate entries (e.g. 干 -> 幹 乾 干) from the embedded text dictionaries
+    pub fn with_alternatives(mut self) -> Self {
+        let sources = [
+            include_str!("dicts/STCharacters.txt"),
+            include_str!("dicts/STPhrases.txt"),
+            include_str!("dicts/TSCharacters.txt"),
+            include_str!("dicts/TSPhrases.txt"),
+            include_str!("dicts/TWPhrases.txt"),
+            include_str!("dicts/TWPhrasesRev.txt"),
+            include_str!("dicts/TWVariants.txt"),
+            include_str!("dicts/TWVariantsRev.txt"),
+            include_str!("dicts/TWVariantsRevPhrases.txt"),
+            include_str!("dicts/HKVariants.txt"),
+            include_str!("dicts/HKVariantsRev.txt"),
+            include_str!("dicts/HKVariantsRevPhrases.txt"),
+            include_str!("dicts/JPShinjitaiCharacters.txt"),
+            include_str!("dicts/JPShinjitaiPhrases.txt"),
+            include_str!("dicts/JPVariants.txt"),
+            include_str!("dicts/JPVariantsRev.txt"),
+        ];
+        let names = self.tables().map(|(name, _)| name);
+        for (name, source) in names.into_iter().zip(sources) {
+            let alternatives =
+                Dictionary::load_alternatives_from_reader(source.as_bytes()).unwrap_or_default();
+            if !alternatives.is_empty() {
+                self.alternatives.insert(name.to_string(), alternatives);
+            }
+        }
+        self
+    }
+
+    // All candidates for a key: the table's current value first, then the other
+    // alternatives in file order. Empty if the table has no entry for the key.
+    pub fn get_all(&self, table: &str, key: &str) -> Vec<&str> {
+        let value = match self
+            .tables()
+            .into_iter()
+            .find(|(name, _)| *name == table)
+            .and_then(|(_, dictionary)| dictionary.get(key))
+        {
+            Some(value) => value.as_str(),
+            None => return Vec::new(),
+        };
+        let mut candidates = vec![value];
+        if let Some(alternatives) = self.alternatives.get(table).and_then(|t| t.get(key)) {
+            candidates.extend(
+                alternatives
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|candidate| *candidate != value),
+            );
+        }
+        candidates
+    }
+
+    pub fn load_dictionary_from_path<P>(filename: P) -> io::Result<HashMap<String, String>>
+    where
+        P: AsRef<Path>,
+    {
+        let file = File::open(filename)?;
+        Dictionary::load_dictionary_from_reader(BufReader::new(file))
+    }
+
+    // Single table in OpenCC text format ("phrase<TAB>translation" per line)
+    pub fn load_dictionary_from_reader<R: BufRead>(
+        reader: R,
+    ) -> io::Result<HashMap<String, String>> {
+        let candidates = Dictionary::load_candidates_from_reader(reader)?;
+        Ok(candidates
+            .into_iter()
+            .map(|(phrase, mut values)| (phrase, values.swap_remove(0)))
+            .collect())
+    }
+
+    // Only the keys with two or more candidates in OpenCC text format
+    pub fn load_alternatives_from_reader<R: BufRead>(
+        reader: R,
+    ) -> io::Result<HashMap<String, Vec<String>>> {
+        let mut candidates = Dictionary::load_candidates_from_reader(reader)?;
+        candidates.retain(|_, values| values.len() > 1);
+        Ok(candidates)
+    }
+
+    // Every candidate per key in OpenCC text format, in file order; the first one
+    // is what conversion uses. Blank lines are skipped, a key without a value is an error.
+    pub fn load_candidates_from_reader<R: BufRead>(
+        reader: R,
+    ) -> io::Result<HashMap<String, Vec<String>>> {
+        let mut candidates = HashMap::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(phrase), Some(first)) => {
+                    let values = std::iter::once(first).chain(parts).map(String::from);
+                    candidates.insert(phrase.to_string(), values.collect());
+                }
+                (Some(_), None) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: expected phrase<TAB>translation", index + 1),
+                    ));
+                }
+                (None, _) => {}
+            }
+        }
+
+        Ok(candidates)
+    }
+
+    pub fn load_dictionary_from_str(
+        dictionary_content: &str,
+    ) -> io::Result<HashMap<String, String>> {
+        Dictionary::load_dictionary_from_reader(dictionary_content.as_bytes())
     }
 
     #[allow(dead_code)]
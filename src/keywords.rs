@@ -0,0 +1,140 @@
+use std::io;
+use std::io::BufRead;
+use std::ops::Range;
+
+use jieba_rs::{Keyword, KeywordExtract, KeywordExtractConfig, TextRank, TfIdf};
+use lazy_static::lazy_static;
+
+use crate::OpenCC;
+
+lazy_static! {
+    static ref TFIDF: TfIdf = TfIdf::default();
+    static ref TEXTRANK: TextRank = TextRank::default();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordSpan {
+    pub keyword: String,
+    pub weight: f64,
+    // Byte ranges of every occurrence in the original input
+    pub ranges: Vec<Range<usize>>,
+}
+
+impl OpenCC {
+    pub fn keyword_extract_textrank(
+        &self,
+        input: &str,
+        top_k: usize,
+        allowed_pos: &[&str],
+    ) -> Vec<String> {
+        self.keyword_weight_textrank(input, top_k, allowed_pos)
+            .into_iter()
+            .map(|k| k.keyword)
+            .collect()
+    }
+
+    pub fn keyword_extract_tfidf(
+        &self,
+        input: &str,
+        top_k: usize,
+        allowed_pos: &[&str],
+    ) -> Vec<String> {
+        self.keyword_weight_tfidf(input, top_k, allowed_pos)
+            .into_iter()
+            .map(|k| k.keyword)
+            .collect()
+    }
+
+    pub fn keyword_weight_textrank(
+        &self,
+        input: &str,
+        top_k: usize,
+        allowed_pos: &[&str],
+    ) -> Vec<Keyword> {
+        TEXTRANK.extract_keywords(&self.jieba, input, top_k, Self::pos_vec(allowed_pos))
+    }
+
+    pub fn keyword_weight_tfidf(
+        &self,
+        input: &str,
+        top_k: usize,
+        allowed_pos: &[&str],
+    ) -> Vec<Keyword> {
+        let tfidf = self.tfidf.as_ref().unwrap_or(&TFIDF);
+        tfidf.extract_keywords(&self.jieba, input, top_k, Self::pos_vec(allowed_pos))
+    }
+
+    pub fn keyword_spans_textrank(
+        &self,
+        input: &str,
+        top_k: usize,
+        allowed_pos: &[&str],
+    ) -> Vec<KeywordSpan> {
+        let keywords = self.keyword_weight_textrank(input, top_k, allowed_pos);
+        self.keyword_spans(input, keywords)
+    }
+
+    pub fn keyword_spans_tfidf(
+        &self,
+        input: &str,
+        top_k: usize,
+        allowed_pos: &[&str],
+    ) -> Vec<KeywordSpan> {
+        let keywords = self.keyword_weight_tfidf(input, top_k, allowed_pos);
+        self.keyword_spans(input, keywords)
+    }
+
+    // Locate keywords on the same (non-HMM) segmentation the extractors use,
+    // so occurrences never overlap or match inside a longer word
+    fn keyword_spans(&self, input: &str, keywords: Vec<Keyword>) -> Vec<KeywordSpan> {
+        let mut spans: Vec<KeywordSpan> = keywords
+            .into_iter()
+            .map(|k| KeywordSpan {
+                keyword: k.keyword,
+                weight: k.weight,
+                ranges: Vec::new(),
+            })
+            .collect();
+        let mut offset = 0;
+        for token in self.jieba.cut(input, false) {
+            let range = offset..offset + token.len();
+            if let Some(span) = spans.iter_mut().find(|span| span.keyword == token) {
+                span.ranges.push(range.clone());
+            }
+            offset = range.end;
+        }
+        spans
+    }
+
+    // Replace jieba's built-in IDF table with a domain-specific one ("word idf" per line).
+    // Lines without a parseable idf are skipped, as jieba does; none at all is an error.
+    pub fn load_idf_dict<R: BufRead>(&mut self, dict: &mut R) -> io::Result<()> {
+        let mut entries = String::new();
+        for line in dict.lines() {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            if let (Some(word), Some(idf)) = (parts.next(), parts.next()) {
+                if idf.parse::<f64>().is_ok() {
+                    entries.push_str(&format!("{} {}\n", word, idf));
+                }
+            }
+        }
+        // jieba's load_dict panics on a table without entries
+        if entries.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "IDF dictionary has no \"word idf\" lines",
+            ));
+        }
+
+        let mut tfidf = TfIdf::new(None::<&mut R>, KeywordExtractConfig::default());
+        tfidf.load_dict(&mut entries.as_bytes())?;
+        self.tfidf = Some(tfidf);
+        Ok(())
+    }
+
+    // Empty allow-list means no POS filtering, e.g. &["n", "ns", "vn"] keeps nouns only
+    fn pos_vec(allowed_pos: &[&str]) -> Vec<String> {
+        allowed_pos.iter().map(|pos| pos.to_string()).collect()
+    }
+}
@@ -1,13 +1,34 @@
-use std::collections::HashMap;
-use std::io::BufReader;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
 
 use jieba_rs::Jieba;
+#[cfg(feature = "keywords")]
+use jieba_rs::TfIdf;
 use lazy_static::lazy_static;
 use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use crate::dictionary_lib::Dictionary;
+use crate::output_sink::OutputSink;
 
+#[cfg(feature = "keywords")]
+pub use jieba_rs::Keyword;
+#[cfg(feature = "keywords")]
+pub use keywords::KeywordSpan;
+
+#[cfg(feature = "arrow")]
+mod arrow;
 pub mod dictionary_lib;
+#[cfg(feature = "keywords")]
+mod keywords;
+pub mod output_sink;
+pub mod utils;
+#[cfg(feature = "watch")]
+pub mod watch;
 
 lazy_static! {
     static ref STRIP_REGEX: Regex = Regex::new(r"[!-/:-@\[-`{-~\t\n\v\f\r 0-9A-Za-z_]").unwrap();
@@ -16,291 +37,924 @@ lazy_static! {
 pub struct OpenCC {
     pub jieba: Jieba,
     dictionary: Dictionary,
+    // Round (table names in precedence order) -> every single-char key of those
+    // tables, first table wins. Derived whenever the dictionary is set.
+    char_tables: HashMap<Vec<&'static str>, HashMap<char, String>>,
+    // Config (lowercase) -> term -> decided conversion
+    user_phrases: HashMap<String, HashMap<String, String>>,
+    #[cfg(feature = "keywords")]
+    tfidf: Option<TfIdf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DictMatch {
+    pub dict_name: &'static str,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupHit {
+    // Conversion round the lookup happens in (0 = first)
+    pub round: usize,
+    pub dict_name: &'static str,
+    // Text looked up: the phrase itself in round 0, the previous round's output after
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenAlternatives {
+    pub token: String,
+    // Fully converted candidates, the first one is what convert() emits
+    pub candidates: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenAnalysis {
+    pub token: String,
+    // Final output of the token after all conversion rounds
+    pub output: String,
+    // Entry that handles the token in the first round, if any
+    pub matched: Option<DictMatch>,
+    // Entries for the same token in every other table
+    pub candidates: Vec<DictMatch>,
+    // Char-by-char result of the first round
+    pub fallback: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageReport {
+    // Tokens containing non-ASCII letters (Han, Kana), punctuation excluded
+    pub total_tokens: usize,
+    pub dictionary_hits: usize,
+    pub char_fallbacks: usize,
+    // Multi-char tokens left to char fallback, most frequent first
+    pub top_unmatched: Vec<(String, usize)>,
+}
+
+type DictRound<'a> = Vec<(&'static str, &'a HashMap<String, String>)>;
+
+// A round's dictionaries for whole-phrase lookups, plus its merged char table
+struct RoundRef<'a> {
+    dicts: Vec<&'a HashMap<String, String>>,
+    char_table: &'a HashMap<char, String>,
+}
+
+const CONFIGS: [&str; 16] = [
+    "s2t", "s2tw", "s2twp", "s2hk", "t2s", "t2tw", "t2twp", "t2hk", "tw2s", "tw2sp", "tw2t",
+    "tw2tp", "hk2s", "hk2t", "t2jp", "jp2t",
+];
+
+type RoundHook<'a> = Box<dyn FnMut(usize, &mut String) + 'a>;
+
+// Callbacks around each conversion round, e.g. s2twp: 0 - st, 1 - tw_phrases, 2 - tw_variants.
+// Called once per round with (round index, whole intermediate text). If a hook changes the
+// text it is re-segmented, and from then on user phrases are no longer kept final.
+#[derive(Default)]
+pub struct RoundHooks<'a> {
+    on_round_start: Option<RoundHook<'a>>,
+    on_round_end: Option<RoundHook<'a>>,
+}
+
+impl<'a> RoundHooks<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_round_start<F: FnMut(usize, &mut String) + 'a>(mut self, hook: F) -> Self {
+        self.on_round_start = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_round_end<F: FnMut(usize, &mut String) + 'a>(mut self, hook: F) -> Self {
+        self.on_round_end = Some(Box::new(hook));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairedSentence {
+    pub index: usize,
+    pub original: String,
+    pub repaired: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairReport {
+    // zho_check code of the majority script: 1 - Traditional, 2 - Simplified, 0 - none
+    pub majority_code: i32,
+    pub total_sentences: usize,
+    pub repaired_sentences: Vec<RepairedSentence>,
 }
 
 impl OpenCC {
     pub fn new() -> Self {
+        Self::with_dictionary(Dictionary::new())
+    }
+
+    // Custom segmentation dictionary in jieba format ("word freq [tag]" per line)
+    pub fn with_jieba_dict<R: BufRead>(dict: &mut R) -> io::Result<Self> {
+        let jieba = Jieba::with_dict(dict).map_err(|err| match err {
+            jieba_rs::Error::Io(err) => err,
+            err => io::Error::new(io::ErrorKind::InvalidData, err),
+        })?;
+
+        Ok(Self::from_parts(jieba, Dictionary::new()))
+    }
+
+    // Swap in a dictionary from a JSON file or a directory of OpenCC .txt files,
+    // keeping the jieba tokenizer and user phrases; on error nothing changes
+    pub fn reload_dictionary<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.set_dictionary(Dictionary::load(path)?);
+        Ok(())
+    }
+
+    pub fn set_dictionary(&mut self, dictionary: Dictionary) {
+        let mut char_tables = HashMap::new();
+        for config in CONFIGS {
+            let rounds = Self::round_names(config).into_iter().flatten();
+            let char_rounds = Self::char_round_names(config).into_iter().flatten();
+            for names in rounds.chain(char_rounds) {
+                char_tables
+                    .entry(names)
+                    .or_insert_with_key(|names: &Vec<&str>| {
+                        let tables = names.iter().filter_map(|name| dictionary.table(name));
+                        Self::build_char_table(tables)
+                    });
+            }
+        }
+        self.char_tables = char_tables;
+        self.dictionary = dictionary;
+    }
+
+    fn build_char_table<'a>(
+        tables: impl Iterator<Item = &'a HashMap<String, String>>,
+    ) -> HashMap<char, String> {
+        let mut char_table = HashMap::new();
+        for table in tables {
+            for (key, value) in table {
+                let mut key_chars = key.chars();
+                if let (Some(ch), None) = (key_chars.next(), key_chars.next()) {
+                    char_table.entry(ch).or_insert_with(|| value.clone());
+                }
+            }
+        }
+        char_table
+    }
+
+    // Embedded dictionary plus a small patch file (see Dictionary::apply_patch)
+    pub fn with_patch<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut dictionary = Dictionary::new();
+        dictionary.apply_patch_from_path(path)?;
+        Ok(Self::with_dictionary(dictionary))
+    }
+
+    pub fn with_dictionary(dictionary: Dictionary) -> Self {
         let dict_hans_hant_txt = include_str!("dictionary_lib/dicts/dict_hans_hant.txt");
         let mut dict_hans_hant = BufReader::new(dict_hans_hant_txt.as_bytes());
         let jieba = Jieba::with_dict(&mut dict_hans_hant).unwrap();
-        let dictionary = Dictionary::new();
 
-        OpenCC { jieba, dictionary }
+        Self::from_parts(jieba, dictionary)
     }
 
-    fn convert_by_slice<'a>(
-        phrases: impl Iterator<Item = &'a str> + 'a,
-        dictionaries: &'a [&HashMap<String, String>],
-    ) -> impl Iterator<Item = String> + 'a {
-        phrases.map(move |phrase| {
-            for dictionary in dictionaries {
-                if let Some(translation) = dictionary.get(phrase) {
-                    return translation.to_string(); // Clone the String translation
+    fn from_parts(jieba: Jieba, dictionary: Dictionary) -> Self {
+        let mut opencc = OpenCC {
+            jieba,
+            dictionary: Dictionary::default(),
+            char_tables: HashMap::new(),
+            user_phrases: HashMap::new(),
+            #[cfg(feature = "keywords")]
+            tfidf: None,
+        };
+        opencc.set_dictionary(dictionary);
+        opencc
+    }
+
+    // Decisions file: "term<TAB>chosen conversion" per line, applied for `config` only
+    // as a top-priority layer that later rounds (e.g. variants) never rewrite.
+    // Split on the first tab only, so a conversion may contain spaces.
+    pub fn load_decisions<P: AsRef<Path>>(&mut self, path: P, config: &str) -> io::Result<()> {
+        if self.config_rounds(config).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unknown config: {}", config),
+            ));
+        }
+        let reader = BufReader::new(File::open(path)?);
+        let mut decisions = HashMap::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match line.split_once('\t') {
+                Some((term, conversion)) if !term.is_empty() => {
+                    decisions.insert(term.to_string(), conversion.to_string());
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("decisions line {}: expected term<TAB>conversion", index + 1),
+                    ))
                 }
             }
-            Self::convert_by_char(phrase, dictionaries)
-        })
-    }
-
-    fn convert_by_string<'a>(
-        phrases: impl Iterator<Item = String> + 'a,
-        dictionaries: &'a [&HashMap<String, String>],
-    ) -> impl Iterator<Item = String> + 'a {
-        phrases.map(move |phrase| {
-            // 整个词转换
-            for dictionary in dictionaries {
-                if let Some(translation) = dictionary.get(&phrase) {
-                    return translation.to_string(); // Clone the String translation
+        }
+        self.add_user_phrases(config, decisions);
+        Ok(())
+    }
+
+    // Phrases converted as given under `config`, other configs are unaffected
+    pub fn add_user_phrases(&mut self, config: &str, phrases: HashMap<String, String>) {
+        let user_phrases = self.user_phrases.entry(config.to_lowercase()).or_default();
+        for (phrase, translation) in phrases {
+            // Keep the term as a single token so the decision can match
+            self.jieba.add_word(&phrase, None, None);
+            user_phrases.insert(phrase, translation);
+        }
+    }
+
+    fn user_phrase(&self, config: &str, phrase: &str) -> Option<&String> {
+        self.user_phrases
+            .get(&config.to_lowercase())
+            .and_then(|user_phrases| user_phrases.get(phrase))
+    }
+
+    fn convert_phrase(phrase: &str, round: &RoundRef) -> String {
+        // 单字直接走逐字转换
+        let mut chars = phrase.chars();
+        if chars.next().is_some() && chars.next().is_none() {
+            return Self::convert_by_char(phrase, round.char_table);
+        }
+        // 整个词转换
+        for dictionary in &round.dicts {
+            if let Some(translation) = dictionary.get(phrase) {
+                return translation.to_string(); // Clone the String translation
+            }
+        }
+        // 逐字转换
+        Self::convert_by_char(phrase, round.char_table)
+    }
+
+    fn convert_by_rounds(&self, input: &str, config: &str) -> String {
+        String::from_iter(self.convert_segments(input, config))
+    }
+
+    fn convert_segments(&self, input: &str, config: &str) -> Vec<String> {
+        self.convert_segments_with_hooks(input, config, &mut RoundHooks::default())
+    }
+
+    fn convert_segments_with_hooks(
+        &self,
+        input: &str,
+        config: &str,
+        hooks: &mut RoundHooks,
+    ) -> Vec<String> {
+        let round_refs = match self.config_rounds(config) {
+            Some(rounds) => self.round_refs(&rounds),
+            None => return Vec::new(),
+        };
+        // User phrases are final: they skip every round
+        let mut segments: Vec<(String, bool)> = self
+            .jieba
+            .cut(input, true)
+            .into_iter()
+            .map(|token| match self.user_phrase(config, token) {
+                Some(user_phrase) => (user_phrase.clone(), true),
+                None => (token.to_string(), false),
+            })
+            .collect();
+        // Each round runs over the whole token list, so hooks see the whole text
+        for (round, round_ref) in round_refs.iter().enumerate() {
+            if let Some(hook) = hooks.on_round_start.as_mut() {
+                segments = self.run_hook(hook, round, segments);
+            }
+            for (segment, is_final) in segments.iter_mut() {
+                if !*is_final {
+                    *segment = Self::convert_phrase(segment, round_ref);
                 }
             }
-            // 逐字转换
-            Self::convert_by_char(&phrase, dictionaries)
-        })
+            if let Some(hook) = hooks.on_round_end.as_mut() {
+                segments = self.run_hook(hook, round, segments);
+            }
+        }
+        segments.into_iter().map(|(segment, _)| segment).collect()
     }
 
-    fn convert_by_char(phrase: &str, dictionaries: &[&HashMap<String, String>]) -> String {
-        let mut phrase_builder = String::new();
-        phrase_builder.reserve(phrase.len());
-        for ch in phrase.chars() {
-            let ch_str = ch.to_string();
-            let mut char_found = false;
-            for dictionary in dictionaries {
-                if let Some(translation) = dictionary.get(&ch_str) {
-                    phrase_builder.push_str(translation);
-                    char_found = true;
+    fn run_hook(
+        &self,
+        hook: &mut RoundHook,
+        round: usize,
+        segments: Vec<(String, bool)>,
+    ) -> Vec<(String, bool)> {
+        let joined = String::from_iter(segments.iter().map(|(segment, _)| segment.as_str()));
+        let mut text = joined.clone();
+        hook(round, &mut text);
+        if text == joined {
+            return segments;
+        }
+        self.jieba
+            .cut(&text, true)
+            .into_iter()
+            .map(|token| (token.to_string(), false))
+            .collect()
+    }
+
+    // One jieba token through every round. User phrases are final: they skip every round.
+    fn convert_token(&self, token: &str, config: &str, round_refs: &[RoundRef]) -> String {
+        match self.user_phrase(config, token) {
+            Some(user_phrase) => user_phrase.clone(),
+            None => round_refs
+                .iter()
+                .fold(token.to_string(), |phrase, round_ref| {
+                    Self::convert_phrase(&phrase, round_ref)
+                }),
+        }
+    }
+
+    pub fn convert_with_hooks(
+        &self,
+        input: &str,
+        config: &str,
+        punctuation: bool,
+        hooks: &mut RoundHooks,
+    ) -> String {
+        let output = String::from_iter(self.convert_segments_with_hooks(input, config, hooks));
+        match Self::punctuation_config(config).filter(|_| punctuation) {
+            Some(punct) => Self::convert_punctuation(&output, punct),
+            None => output,
+        }
+    }
+
+    // Same result as convert(), written segment by segment into the sink
+    pub fn convert_into<S: OutputSink>(
+        &self,
+        input: &str,
+        config: &str,
+        punctuation: bool,
+        sink: &mut S,
+    ) -> io::Result<()> {
+        let round_refs = match self.config_rounds(config) {
+            Some(rounds) => self.round_refs(&rounds),
+            None => return sink.finish(),
+        };
+        let punctuation_config = Self::punctuation_config(config).filter(|_| punctuation);
+        // Each token is written as soon as it is converted, nothing is collected
+        for token in self.jieba.cut(input, true) {
+            let segment = self.convert_token(token, config, &round_refs);
+            match punctuation_config {
+                Some(punct) => sink.write_segment(&Self::convert_punctuation(&segment, punct))?,
+                None => sink.write_segment(&segment)?,
+            }
+        }
+        sink.finish()
+    }
+
+    // Convert, then fit into max_display_width terminal columns with a trailing "…".
+    // Cuts between converted tokens so no phrase is left half-converted; only when
+    // not even the first token fits is it cut on a grapheme boundary.
+    pub fn convert_and_truncate(
+        &self,
+        input: &str,
+        config: &str,
+        max_display_width: usize,
+    ) -> String {
+        const ELLIPSIS: &str = "…";
+        let segments = self.convert_segments(input, config);
+        let total_width: usize = segments.iter().map(|segment| segment.width()).sum();
+        if total_width <= max_display_width {
+            return String::from_iter(segments);
+        }
+        if max_display_width < ELLIPSIS.width() {
+            return String::new();
+        }
+
+        let budget = max_display_width - ELLIPSIS.width();
+        let mut output = String::new();
+        let mut width = 0;
+        for segment in &segments {
+            let segment_width = segment.width();
+            if width + segment_width > budget {
+                break;
+            }
+            output.push_str(segment);
+            width += segment_width;
+        }
+        if output.is_empty() {
+            for grapheme in segments[0].graphemes(true) {
+                let grapheme_width = grapheme.width();
+                if width + grapheme_width > budget {
                     break;
                 }
+                output.push_str(grapheme);
+                width += grapheme_width;
             }
-            if !char_found {
-                phrase_builder.push_str(&ch_str);
+        }
+        output.truncate(output.trim_end().len());
+        output.push_str(ELLIPSIS);
+        output
+    }
+
+    fn convert_by_char(phrase: &str, char_table: &HashMap<char, String>) -> String {
+        let mut phrase_builder = String::new();
+        phrase_builder.reserve(phrase.len());
+        for ch in phrase.chars() {
+            match char_table.get(&ch) {
+                Some(translation) => phrase_builder.push_str(translation),
+                None => phrase_builder.push(ch),
             }
         }
         phrase_builder
     }
 
     pub fn s2t(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.st_phrases, &self.dictionary.st_characters];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
+        let output = self.convert_by_rounds(input, "s2t");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "s")
+            Self::convert_punctuation(&output, "s")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn t2s(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.ts_phrases, &self.dictionary.ts_characters];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
+        let output = self.convert_by_rounds(input, "t2s");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "t")
+            Self::convert_punctuation(&output, "t")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn s2tw(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.st_phrases, &self.dictionary.st_characters];
-        let dict_refs_round_2 = [&self.dictionary.tw_variants];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
+        let output = self.convert_by_rounds(input, "s2tw");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "s")
+            Self::convert_punctuation(&output, "s")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn tw2s(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [
-            &self.dictionary.tw_variants_rev,
-            &self.dictionary.tw_variants_rev_phrases,
-        ];
-        let dict_refs_round_2 = [&self.dictionary.ts_phrases, &self.dictionary.ts_characters];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
+        let output = self.convert_by_rounds(input, "tw2s");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "t")
+            Self::convert_punctuation(&output, "t")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn s2twp(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.st_phrases, &self.dictionary.st_characters];
-        let dict_refs_round_2 = [&self.dictionary.tw_phrases];
-        let dict_refs_round_3 = [&self.dictionary.tw_variants];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
-        let output = Self::convert_by_string(output, &dict_refs_round_3);
+        let output = self.convert_by_rounds(input, "s2twp");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "s")
+            Self::convert_punctuation(&output, "s")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn tw2sp(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [
-            &self.dictionary.tw_variants_rev,
-            &self.dictionary.tw_variants_rev_phrases,
-        ];
-        let dict_refs_round_2 = [&self.dictionary.tw_phrases_rev];
-        let dict_refs_round_3 = [&self.dictionary.ts_phrases, &self.dictionary.ts_characters];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
-        let output = Self::convert_by_string(output, &dict_refs_round_3);
+        let output = self.convert_by_rounds(input, "tw2sp");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "t")
+            Self::convert_punctuation(&output, "t")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn s2hk(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.st_phrases, &self.dictionary.st_characters];
-        let dict_refs_round_2 = [&self.dictionary.hk_variants];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
+        let output = self.convert_by_rounds(input, "s2hk");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "s")
+            Self::convert_punctuation(&output, "s")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn hk2s(&self, input: &str, punctuation: bool) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [
-            &self.dictionary.hk_variants_rev_phrases,
-            &self.dictionary.hk_variants_rev,
-        ];
-        let dict_refs_round_2 = [&self.dictionary.ts_phrases, &self.dictionary.ts_characters];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
+        let output = self.convert_by_rounds(input, "hk2s");
         if punctuation {
-            Self::convert_punctuation(String::from_iter(output).as_str(), "h")
+            Self::convert_punctuation(&output, "h")
         } else {
-            String::from_iter(output)
+            output
         }
     }
 
     pub fn t2tw(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.tw_variants];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        String::from_iter(output)
+        self.convert_by_rounds(input, "t2tw")
     }
 
     pub fn t2twp(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.tw_phrases];
-        let dict_refs_round_2 = [&self.dictionary.tw_variants];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
-        String::from_iter(output)
+        self.convert_by_rounds(input, "t2twp")
     }
 
     pub fn tw2t(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [
-            &self.dictionary.tw_variants_rev,
-            &self.dictionary.tw_variants_rev_phrases,
-        ];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        String::from_iter(output)
+        self.convert_by_rounds(input, "tw2t")
     }
 
     pub fn tw2tp(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [
-            &self.dictionary.tw_variants_rev,
-            &self.dictionary.tw_variants_rev_phrases,
-        ];
-        let dict_refs_round_2 = [&self.dictionary.tw_phrases_rev];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        let output = Self::convert_by_string(output, &dict_refs_round_2);
-        String::from_iter(output)
+        self.convert_by_rounds(input, "tw2tp")
     }
 
     pub fn t2hk(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.hk_variants];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        String::from_iter(output)
+        self.convert_by_rounds(input, "t2hk")
     }
 
     pub fn hk2t(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [
-            &self.dictionary.hk_variants_rev_phrases,
-            &self.dictionary.hk_variants_rev,
-        ];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-        String::from_iter(output)
+        self.convert_by_rounds(input, "hk2t")
     }
 
     pub fn t2jp(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [&self.dictionary.jp_variants];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
-
-        String::from_iter(output)
+        self.convert_by_rounds(input, "t2jp")
     }
 
     pub fn jp2t(&self, input: &str) -> String {
-        let phrases = self.jieba.cut(input, true);
-        let dict_refs = [
-            &self.dictionary.jps_phrases,
-            &self.dictionary.jps_characters,
-            &self.dictionary.jp_variants_rev,
-        ];
-        let output = Self::convert_by_slice(phrases.into_iter(), &dict_refs);
+        self.convert_by_rounds(input, "jp2t")
+    }
 
-        String::from_iter(output)
+    pub fn convert_chars(&self, input: &str, config: &str) -> String {
+        let rounds = match Self::char_round_names(config) {
+            Some(names) => self.rounds_for(names),
+            None => return String::new(),
+        };
+        // 逐字转换, no segmentation
+        self.round_refs(&rounds)
+            .iter()
+            .fold(input.to_string(), |text, round| {
+                Self::convert_by_char(&text, round.char_table)
+            })
     }
 
-    fn st(&self, input: &str) -> String {
-        let dict_refs = [&self.dictionary.st_characters];
-        let output = Self::convert_by_char(input, &dict_refs);
-        output
+    pub fn convert(&self, input: &str, config: &str, punctuation: bool) -> String {
+        match config.to_lowercase().as_str() {
+            "s2t" => self.s2t(input, punctuation),
+            "s2tw" => self.s2tw(input, punctuation),
+            "s2twp" => self.s2twp(input, punctuation),
+            "s2hk" => self.s2hk(input, punctuation),
+            "t2s" => self.t2s(input, punctuation),
+            "t2tw" => self.t2tw(input),
+            "t2twp" => self.t2twp(input),
+            "t2hk" => self.t2hk(input),
+            "tw2s" => self.tw2s(input, punctuation),
+            "tw2sp" => self.tw2sp(input, punctuation),
+            "tw2t" => self.tw2t(input),
+            "tw2tp" => self.tw2tp(input),
+            "hk2s" => self.hk2s(input, punctuation),
+            "hk2t" => self.hk2t(input),
+            "jp2t" => self.jp2t(input),
+            "t2jp" => self.t2jp(input),
+            _ => String::new(),
+        }
     }
 
-    fn ts(&self, input: &str) -> String {
-        let dict_refs = [&self.dictionary.ts_characters];
-        let output = Self::convert_by_char(input, &dict_refs);
-        output
+    pub fn add_word(&mut self, word: &str, freq: Option<usize>, tag: Option<&str>) -> usize {
+        self.jieba.add_word(word, freq, tag)
     }
 
-    pub fn convert(&self, input: &str, config: &str, punctuation: bool) -> String {
-        let result;
+    pub fn suggest_freq(&self, segment: &str) -> usize {
+        self.jieba.suggest_freq(segment)
+    }
 
-        match config.to_lowercase().as_str() {
-            "s2t" => result = self.s2t(input, punctuation),
-            "s2tw" => result = self.s2tw(input, punctuation),
-            "s2twp" => result = self.s2twp(input, punctuation),
-            "s2hk" => result = self.s2hk(input, punctuation),
-            "t2s" => result = self.t2s(input, punctuation),
-            "t2tw" => result = self.t2tw(input),
-            "t2twp" => result = self.t2twp(input),
-            "t2hk" => result = self.t2hk(input),
-            "tw2s" => result = self.tw2s(input, punctuation),
-            "tw2sp" => result = self.tw2sp(input, punctuation),
-            "tw2t" => result = self.tw2t(input),
-            "tw2tp" => result = self.tw2tp(input),
-            "hk2s" => result = self.hk2s(input, punctuation),
-            "hk2t" => result = self.hk2t(input),
-            "jp2t" => result = self.jp2t(input),
-            "t2jp" => result = self.t2jp(input),
-            _ => result = String::new(),
-        }
-        result
+    pub fn jieba_cut(&self, input: &str, hmm: bool) -> Vec<String> {
+        self.jieba
+            .cut(input, hmm)
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    pub fn jieba_cut_filtered(
+        &self,
+        input: &str,
+        hmm: bool,
+        stop_words: &HashSet<String>,
+    ) -> Vec<String> {
+        self.jieba
+            .cut(input, hmm)
+            .into_iter()
+            .filter(|s| !s.trim().is_empty() && !stop_words.contains(*s))
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    pub fn jieba_cut_for_search(&self, input: &str, hmm: bool) -> Vec<String> {
+        self.jieba
+            .cut_for_search(input, hmm)
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    pub fn jieba_tag(&self, input: &str, hmm: bool) -> Vec<(String, String)> {
+        self.jieba
+            .tag(input, hmm)
+            .into_iter()
+            .map(|t| (t.word.to_string(), t.tag.to_string()))
+            .collect()
+    }
+
+    pub fn summarize(&self, input: &str, n_sentences: usize) -> Vec<String> {
+        let sentences: Vec<&str> = split_sentences(input)
+            .into_iter()
+            .map(|sentence| sentence.trim())
+            .filter(|sentence| !sentence.is_empty())
+            .collect();
+        if sentences.len() <= n_sentences {
+            return sentences.into_iter().map(|s| s.to_string()).collect();
+        }
+        // Sentence word sets, single chars and punctuation carry little meaning
+        let words: Vec<HashSet<&str>> = sentences
+            .iter()
+            .map(|sentence| {
+                self.jieba
+                    .cut(sentence, false)
+                    .into_iter()
+                    .filter(|word| word.chars().count() > 1)
+                    .collect()
+            })
+            .collect();
+        // TextRank edge weight: shared words normalized by sentence lengths
+        let n = sentences.len();
+        let mut weights = vec![vec![0.0f64; n]; n];
+        for i in 0..n {
+            for j in i + 1..n {
+                let common = words[i].intersection(&words[j]).count();
+                let norm = (words[i].len() as f64).ln() + (words[j].len() as f64).ln();
+                if common > 0 && norm > 0.0 {
+                    weights[i][j] = common as f64 / norm;
+                    weights[j][i] = weights[i][j];
+                }
+            }
+        }
+        let out_sums: Vec<f64> = weights.iter().map(|row| row.iter().sum()).collect();
+        let damping = 0.85;
+        let mut scores = vec![1.0f64; n];
+        for _ in 0..30 {
+            scores = (0..n)
+                .map(|i| {
+                    let rank: f64 = (0..n)
+                        .filter(|&j| out_sums[j] > 0.0)
+                        .map(|j| weights[j][i] / out_sums[j] * scores[j])
+                        .sum();
+                    (1.0 - damping) + damping * rank
+                })
+                .collect();
+        }
+        let mut ranked: Vec<usize> = (0..n).collect();
+        ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]).then(a.cmp(&b)));
+        ranked.truncate(n_sentences);
+        // Back to original order
+        ranked.sort_unstable();
+        ranked
+            .into_iter()
+            .map(|i| sentences[i].to_string())
+            .collect()
+    }
+
+    // Table names per round, in the same order the conversion methods apply them
+    fn round_names(config: &str) -> Option<Vec<Vec<&'static str>>> {
+        let st = vec!["st_phrases", "st_characters"];
+        let ts = vec!["ts_phrases", "ts_characters"];
+        let tw_rev = vec!["tw_variants_rev", "tw_variants_rev_phrases"];
+        let hk_rev = vec!["hk_variants_rev_phrases", "hk_variants_rev"];
+        let tw_variants = vec!["tw_variants"];
+        let tw_phrases = vec!["tw_phrases"];
+        let tw_phrases_rev = vec!["tw_phrases_rev"];
+        let hk_variants = vec!["hk_variants"];
+        let rounds = match config.to_lowercase().as_str() {
+            "s2t" => vec![st],
+            "s2tw" => vec![st, tw_variants],
+            "s2twp" => vec![st, tw_phrases, tw_variants],
+            "s2hk" => vec![st, hk_variants],
+            "t2s" => vec![ts],
+            "t2tw" => vec![tw_variants],
+            "t2twp" => vec![tw_phrases, tw_variants],
+            "t2hk" => vec![hk_variants],
+            "tw2s" => vec![tw_rev, ts],
+            "tw2sp" => vec![tw_rev, tw_phrases_rev, ts],
+            "tw2t" => vec![tw_rev],
+            "tw2tp" => vec![tw_rev, tw_phrases_rev],
+            "hk2s" => vec![hk_rev, ts],
+            "hk2t" => vec![hk_rev],
+            "t2jp" => vec![vec!["jp_variants"]],
+            "jp2t" => vec![vec!["jps_phrases", "jps_characters", "jp_variants_rev"]],
+            _ => return None,
+        };
+        Some(rounds)
+    }
+
+    // Same rounds without the phrase tables, for convert_chars
+    fn char_round_names(config: &str) -> Option<Vec<Vec<&'static str>>> {
+        let rounds = Self::round_names(config)?
+            .into_iter()
+            .map(|names| {
+                names
+                    .into_iter()
+                    .filter(|name| !name.contains("phrases"))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|names| !names.is_empty())
+            .collect();
+        Some(rounds)
+    }
+
+    fn config_rounds(&self, config: &str) -> Option<Vec<DictRound<'_>>> {
+        Some(self.rounds_for(Self::round_names(config)?))
+    }
+
+    fn rounds_for(&self, round_names: Vec<Vec<&'static str>>) -> Vec<DictRound<'_>> {
+        round_names
+            .into_iter()
+            .map(|names| {
+                names
+                    .into_iter()
+                    .filter_map(|name| Some((name, self.dictionary.table(name)?)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn round_refs<'a>(&'a self, rounds: &[DictRound<'a>]) -> Vec<RoundRef<'a>> {
+        rounds
+            .iter()
+            .map(|round| {
+                let names: Vec<&'static str> = round.iter().map(|(name, _)| *name).collect();
+                RoundRef {
+                    dicts: round.iter().map(|(_, dict)| *dict).collect(),
+                    // Every round comes from round_names, so set_dictionary built its table
+                    char_table: &self.char_tables[&names],
+                }
+            })
+            .collect()
+    }
+
+    pub fn analyze(&self, input: &str, config: &str) -> Vec<TokenAnalysis> {
+        let rounds = match self.config_rounds(config) {
+            Some(rounds) => rounds,
+            None => return Vec::new(),
+        };
+        let round_refs = self.round_refs(&rounds);
+        let first_round = &rounds[0];
+
+        self.jieba
+            .cut(input, true)
+            .into_iter()
+            .map(|token| {
+                let user_phrase = self.user_phrase(config, token).map(|value| DictMatch {
+                    dict_name: "user_phrases",
+                    value: value.clone(),
+                });
+                let matched = user_phrase.or_else(|| {
+                    first_round.iter().find_map(|(dict_name, dict)| {
+                        dict.get(token).map(|value| DictMatch {
+                            dict_name,
+                            value: value.clone(),
+                        })
+                    })
+                });
+                let matched_name = matched.as_ref().map(|m| m.dict_name);
+                let candidates = self
+                    .dictionary
+                    .tables()
+                    .into_iter()
+                    .filter(|(dict_name, _)| Some(*dict_name) != matched_name)
+                    .filter_map(|(dict_name, dict)| {
+                        dict.get(token).map(|value| DictMatch {
+                            dict_name,
+                            value: value.clone(),
+                        })
+                    })
+                    .collect();
+                let output = match self.user_phrase(config, token) {
+                    Some(user_phrase) => user_phrase.clone(),
+                    None => round_refs
+                        .iter()
+                        .fold(token.to_string(), |text, dict_refs| {
+                            Self::convert_phrase(&text, dict_refs)
+                        }),
+                };
+                TokenAnalysis {
+                    token: token.to_string(),
+                    output,
+                    matched,
+                    candidates,
+                    fallback: Self::convert_by_char(token, round_refs[0].char_table),
+                }
+            })
+            .collect()
+    }
+
+    // Per token, every conversion the dictionaries allow (needs Dictionary::with_alternatives)
+    pub fn convert_with_alternatives(&self, input: &str, config: &str) -> Vec<TokenAlternatives> {
+        let rounds = match self.config_rounds(config) {
+            Some(rounds) => rounds,
+            None => return Vec::new(),
+        };
+        let round_refs = self.round_refs(&rounds);
+
+        self.jieba
+            .cut(input, true)
+            .into_iter()
+            .map(|token| {
+                if let Some(user_phrase) = self.user_phrase(config, token) {
+                    return TokenAlternatives {
+                        token: token.to_string(),
+                        candidates: vec![user_phrase.clone()],
+                    };
+                }
+                let first_round: Vec<String> = rounds[0]
+                    .iter()
+                    .find(|(_, dict)| dict.contains_key(token))
+                    .map(|(dict_name, _)| self.dictionary.get_all(dict_name, token))
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(String::from)
+                    .collect();
+                let first_round = if first_round.is_empty() {
+                    vec![Self::convert_phrase(token, &round_refs[0])]
+                } else {
+                    first_round
+                };
+
+                let mut candidates: Vec<String> = Vec::new();
+                for candidate in first_round {
+                    let converted = round_refs[1..].iter().fold(candidate, |text, dict_refs| {
+                        Self::convert_phrase(&text, dict_refs)
+                    });
+                    if !candidates.contains(&converted) {
+                        candidates.push(converted);
+                    }
+                }
+                TokenAlternatives {
+                    token: token.to_string(),
+                    candidates,
+                }
+            })
+            .collect()
+    }
+
+    // Whole-phrase hits for a token, in precedence order: within a round the first
+    // hit is the one applied. No hit in a round means char-by-char fallback.
+    pub fn lookup(&self, phrase: &str, config: &str) -> Vec<LookupHit> {
+        let rounds = match self.config_rounds(config) {
+            Some(rounds) => rounds,
+            None => return Vec::new(),
+        };
+        if let Some(value) = self.user_phrase(config, phrase) {
+            return vec![LookupHit {
+                round: 0,
+                dict_name: "user_phrases",
+                key: phrase.to_string(),
+                value: value.clone(),
+            }];
+        }
+
+        let round_refs = self.round_refs(&rounds);
+        let mut hits = Vec::new();
+        let mut key = phrase.to_string();
+        for (round, dicts) in rounds.iter().enumerate() {
+            hits.extend(dicts.iter().filter_map(|(dict_name, dict)| {
+                dict.get(&key).map(|value| LookupHit {
+                    round,
+                    dict_name,
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+            }));
+            key = Self::convert_phrase(&key, &round_refs[round]);
+        }
+        hits
+    }
+
+    pub fn coverage(&self, input: &str, config: &str, top_n: usize) -> CoverageReport {
+        let mut report = CoverageReport {
+            total_tokens: 0,
+            dictionary_hits: 0,
+            char_fallbacks: 0,
+            top_unmatched: Vec::new(),
+        };
+        let mut unmatched: HashMap<String, usize> = HashMap::new();
+        for analysis in self.analyze(input, config) {
+            if !analysis
+                .token
+                .chars()
+                .any(|ch| ch.is_alphabetic() && !ch.is_ascii())
+            {
+                continue;
+            }
+            report.total_tokens += 1;
+            if analysis.matched.is_some() {
+                report.dictionary_hits += 1;
+            } else {
+                report.char_fallbacks += 1;
+                if analysis.token.chars().count() > 1 {
+                    *unmatched.entry(analysis.token).or_insert(0) += 1;
+                }
+            }
+        }
+        let mut top_unmatched: Vec<(String, usize)> = unmatched.into_iter().collect();
+        top_unmatched.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_unmatched.truncate(top_n);
+        report.top_unmatched = top_unmatched;
+        report
     }
 
     pub fn zho_check(&self, input: &str) -> i32 {
@@ -308,78 +962,158 @@ impl OpenCC {
             return 0;
         }
         let _strip_text = STRIP_REGEX.replace_all(input, "");
-        let max_bytes = find_max_utf8_length(_strip_text.as_ref(), 200);
+        let max_bytes = utils::find_max_utf8_length(_strip_text.as_ref(), 200);
         let strip_text = &_strip_text[..max_bytes];
-        let code;
-        if strip_text != &self.ts(strip_text) {
-            code = 1;
+        if strip_text != self.convert_chars(strip_text, "t2s") {
+            1
+        } else if strip_text != self.convert_chars(strip_text, "s2t") {
+            2
         } else {
-            if strip_text != &self.st(strip_text) {
-                code = 2;
-            } else {
-                code = 0;
+            0
+        }
+    }
+
+    pub fn repair(&self, input: &str) -> (String, RepairReport) {
+        let sentences = split_sentences(input);
+        let codes: Vec<i32> = sentences.iter().map(|s| self.zho_check(s)).collect();
+        // Weight each script by its character count, so a short title can't outvote the body
+        let mut weights = [0usize; 3];
+        for (sentence, &code) in sentences.iter().zip(&codes) {
+            weights[code as usize] += sentence.chars().count();
+        }
+        let majority_code = if weights[1] == 0 && weights[2] == 0 {
+            0
+        } else if weights[1] >= weights[2] {
+            1
+        } else {
+            2
+        };
+
+        let mut output = String::with_capacity(input.len());
+        let mut repaired_sentences = Vec::new();
+        // A sentence needs repair when converting it toward the majority changes it,
+        // which also catches sentences mixing both scripts
+        for (index, sentence) in sentences.iter().enumerate() {
+            let repaired = match majority_code {
+                1 => self.s2t(sentence, false),
+                2 => self.t2s(sentence, false),
+                _ => sentence.to_string(),
+            };
+            if repaired != *sentence {
+                repaired_sentences.push(RepairedSentence {
+                    index,
+                    original: sentence.to_string(),
+                    repaired: repaired.clone(),
+                });
             }
+            output.push_str(&repaired);
         }
-        code
+
+        let report = RepairReport {
+            majority_code,
+            total_sentences: sentences.len(),
+            repaired_sentences,
+        };
+        (output, report)
     }
 
     fn convert_punctuation(sv: &str, config: &str) -> String {
-        let mut s2t_punctuation_chars: HashMap<&str, &str> = HashMap::new();
-        s2t_punctuation_chars.insert("“", "「");
-        s2t_punctuation_chars.insert("”", "」");
-        s2t_punctuation_chars.insert("‘", "『");
-        s2t_punctuation_chars.insert("’", "』");
-
-        let output_text;
+        let s2t_punctuation_chars = [('“', '「'), ('”', '」'), ('‘', '『'), ('’', '』')];
 
         if config.starts_with('s') {
-            let s2t_pattern = s2t_punctuation_chars.keys().cloned().collect::<String>();
-            let s2t_regex = Regex::new(&format!("[{}]", s2t_pattern)).unwrap();
-            output_text = s2t_regex
-                .replace_all(sv, |caps: &regex::Captures| {
-                    s2t_punctuation_chars[caps.get(0).unwrap().as_str()]
+            sv.chars()
+                .map(|ch| {
+                    s2t_punctuation_chars
+                        .iter()
+                        .find(|(s, _)| *s == ch)
+                        .map_or(ch, |(_, t)| *t)
                 })
-                .into_owned();
+                .collect()
         } else {
-            let mut t2s_punctuation_chars: HashMap<&str, &str> = HashMap::new();
-            for (key, value) in s2t_punctuation_chars.iter() {
-                t2s_punctuation_chars.insert(value, key);
-            }
-            let t2s_pattern = t2s_punctuation_chars.keys().cloned().collect::<String>();
-            let t2s_regex = Regex::new(&format!("[{}]", t2s_pattern)).unwrap();
-            output_text = t2s_regex
-                .replace_all(sv, |caps: &regex::Captures| {
-                    t2s_punctuation_chars[caps.get(0).unwrap().as_str()]
+            sv.chars()
+                .map(|ch| {
+                    s2t_punctuation_chars
+                        .iter()
+                        .find(|(_, t)| *t == ch)
+                        .map_or(ch, |(s, _)| *s)
                 })
-                .into_owned();
+                .collect()
         }
-        output_text
     }
-}
 
-pub fn find_max_utf8_length(sv: &str, max_byte_count: usize) -> usize {
-    // 1. No longer than max byte count
-    if sv.len() <= max_byte_count {
-        return sv.len();
+    // Punctuation direction used by each config, None if the config has no punctuation option
+    fn punctuation_config(config: &str) -> Option<&'static str> {
+        match config.to_lowercase().as_str() {
+            "s2t" | "s2tw" | "s2twp" | "s2hk" => Some("s"),
+            "t2s" | "tw2s" | "tw2sp" => Some("t"),
+            "hk2s" => Some("h"),
+            _ => None,
+        }
     }
-    // 2. Longer than byte count
-    let mut byte_count = max_byte_count;
-    while byte_count > 0 && (sv.as_bytes()[byte_count] & 0b11000000) == 0b10000000 {
-        byte_count -= 1;
+}
+
+impl Default for OpenCC {
+    fn default() -> Self {
+        Self::new()
     }
-    byte_count
 }
 
-pub fn format_thousand(n: i32) -> String {
-    let mut result_str = n.to_string();
-    let mut offset = result_str.len() % 3;
-    if offset == 0 {
-        offset = 3;
+fn split_sentences(input: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for (i, ch) in input.char_indices() {
+        if matches!(ch, '。' | '！' | '？' | '；' | '!' | '?' | ';' | '\n') {
+            let end = i + ch.len_utf8();
+            sentences.push(&input[start..end]);
+            start = end;
+        }
+    }
+    if start < input.len() {
+        sentences.push(&input[start..]);
     }
+    sentences
+}
 
-    while offset < result_str.len() {
-        result_str.insert(offset, ',');
-        offset += 4; // Including the added comma
+// Built-in Chinese stop words (Simplified and Traditional forms, common punctuation)
+#[cfg(feature = "stop-words")]
+pub fn default_stop_words() -> HashSet<String> {
+    include_str!("dictionary_lib/dicts/stop_words.txt")
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[cfg(feature = "collation")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collation {
+    Pinyin,
+    Stroke,
+}
+
+// Sort Chinese words for human review, byte order is meaningless for Han characters
+#[cfg(feature = "collation")]
+pub fn sort_collated<T: AsRef<str>>(items: &mut [T], collation: Collation) {
+    use icu_collator::{Collator, CollatorOptions};
+    use icu_locid::Locale;
+
+    let locale: Locale = match collation {
+        Collation::Pinyin => "zh-u-co-pinyin",
+        Collation::Stroke => "zh-u-co-stroke",
     }
-    result_str
+    .parse()
+    .unwrap();
+    let collator = Collator::try_new(&(&locale).into(), CollatorOptions::new()).unwrap();
+    items.sort_by(|a, b| collator.compare(a.as_ref(), b.as_ref()));
+}
+
+// Moved to utils, kept so existing call sites still compile
+#[deprecated(note = "use opencc_jieba_rs::utils::find_max_utf8_length")]
+pub fn find_max_utf8_length(sv: &str, max_byte_count: usize) -> usize {
+    utils::find_max_utf8_length(sv, max_byte_count)
+}
+
+#[deprecated(note = "use opencc_jieba_rs::utils::format_thousand")]
+pub fn format_thousand(n: i32) -> String {
+    utils::format_thousand(n)
 }
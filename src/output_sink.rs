@@ -0,0 +1,51 @@
+use std::io;
+use std::io::Write;
+use std::sync::mpsc::Sender;
+
+// Destination for converted text, written segment by segment
+pub trait OutputSink {
+    fn write_segment(&mut self, segment: &str) -> io::Result<()>;
+
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl OutputSink for String {
+    fn write_segment(&mut self, segment: &str) -> io::Result<()> {
+        self.push_str(segment);
+        Ok(())
+    }
+}
+
+// Any io::Write (File, TcpStream, Stdout...), flushed on finish
+pub struct WriteSink<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> WriteSink<W> {
+    pub fn new(writer: W) -> Self {
+        WriteSink { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> OutputSink for WriteSink<W> {
+    fn write_segment(&mut self, segment: &str) -> io::Result<()> {
+        self.writer.write_all(segment.as_bytes())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl OutputSink for Sender<String> {
+    fn write_segment(&mut self, segment: &str) -> io::Result<()> {
+        self.send(segment.to_string())
+            .map_err(|err| io::Error::new(io::ErrorKind::BrokenPipe, err))
+    }
+}
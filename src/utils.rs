@@ -0,0 +1,26 @@
+pub fn find_max_utf8_length(sv: &str, max_byte_count: usize) -> usize {
+    // 1. No longer than max byte count
+    if sv.len() <= max_byte_count {
+        return sv.len();
+    }
+    // 2. Longer than byte count
+    let mut byte_count = max_byte_count;
+    while byte_count > 0 && (sv.as_bytes()[byte_count] & 0b11000000) == 0b10000000 {
+        byte_count -= 1;
+    }
+    byte_count
+}
+
+pub fn format_thousand(n: i32) -> String {
+    let mut result_str = n.to_string();
+    let mut offset = result_str.len() % 3;
+    if offset == 0 {
+        offset = 3;
+    }
+
+    while offset < result_str.len() {
+        result_str.insert(offset, ',');
+        offset += 4; // Including the added comma
+    }
+    result_str
+}
@@ -0,0 +1,66 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::dictionary_lib::Dictionary;
+use crate::OpenCC;
+
+// Reloads the dictionary in the background whenever the file / directory changes.
+// The new Dictionary is applied on the caller's thread with apply(), so conversions
+// never see a half-loaded dictionary.
+pub struct DictionaryWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<io::Result<Dictionary>>,
+}
+
+impl DictionaryWatcher {
+    pub fn new<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path: PathBuf = path.as_ref().to_path_buf();
+        let (sender, receiver) = channel();
+        let dictionary_path = path.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                let relevant = dictionary_path.is_dir()
+                    || event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name() == dictionary_path.file_name());
+                if relevant && matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                    let _ = sender.send(Dictionary::load(&dictionary_path));
+                }
+            }
+        })
+        .map_err(io::Error::other)?;
+        // Watch a file through its directory: editors often save by renaming over it
+        let watch_path = match path.parent() {
+            Some(parent) if path.is_file() && !parent.as_os_str().is_empty() => parent,
+            _ => &path,
+        };
+        watcher
+            .watch(watch_path, RecursiveMode::NonRecursive)
+            .map_err(io::Error::other)?;
+
+        Ok(DictionaryWatcher {
+            _watcher: watcher,
+            receiver,
+        })
+    }
+
+    // Apply the latest reloaded dictionary, if any. Returns Ok(true) if swapped;
+    // a failed reload (e.g. file caught mid-write) is returned and the old one kept.
+    pub fn apply(&self, opencc: &mut OpenCC) -> io::Result<bool> {
+        let mut latest = None;
+        while let Ok(result) = self.receiver.try_recv() {
+            latest = Some(result);
+        }
+        match latest {
+            Some(dictionary) => {
+                opencc.set_dictionary(dictionary?);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use opencc_jieba_rs::{dictionary_lib, format_thousand, OpenCC};
+use opencc_jieba_rs::output_sink::WriteSink;
+use opencc_jieba_rs::utils::format_thousand;
+use opencc_jieba_rs::{dictionary_lib, OpenCC, RoundHooks};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::{HashMap, HashSet};
     use std::fs;
+    use std::sync::mpsc;
 
     #[test]
     fn zho_check_test() {
@@ -59,6 +63,95 @@ mod tests {
         assert_eq!(actual_output, expected_output);
     }
 
+    #[test]
+    fn convert_chars_test() {
+        let input = "龙马精神_数据库.txt";
+        let expected_output = "龍馬精神_數據庫.txt";
+        let opencc = OpenCC::new();
+        let actual_output = opencc.convert_chars(input, "s2t");
+        assert_eq!(actual_output, expected_output);
+        assert_eq!(opencc.convert_chars(&actual_output, "t2s"), input);
+        assert_eq!(opencc.convert_chars(input, "unknown"), "");
+    }
+
+    #[test]
+    fn repair_test() {
+        let input = "龍馬精神，萬事如意。天下太平！国泰民安。";
+        let expected_output = "龍馬精神，萬事如意。天下太平！國泰民安。";
+        let opencc = OpenCC::new();
+        let (actual_output, report) = opencc.repair(input);
+        assert_eq!(actual_output, expected_output);
+        assert_eq!(report.majority_code, 1);
+        assert_eq!(report.total_sentences, 3);
+        assert_eq!(report.repaired_sentences.len(), 1);
+        assert_eq!(report.repaired_sentences[0].index, 2);
+
+        // Half-converted sentence: zho_check calls it Traditional, it still gets fixed
+        let input = "龍馬精神，萬事如意。天下太平，国泰民安，萬事如意。一帆風順。";
+        let (actual_output, report) = opencc.repair(input);
+        assert_eq!(
+            actual_output,
+            "龍馬精神，萬事如意。天下太平，國泰民安，萬事如意。一帆風順。"
+        );
+        assert_eq!(report.repaired_sentences.len(), 1);
+        assert_eq!(report.repaired_sentences[0].index, 1);
+    }
+
+    #[test]
+    fn analyze_test() {
+        let input = "意大利罗浮宫里收藏的蒙娜丽莎的微笑画像是旷世之作。";
+        let opencc = OpenCC::new();
+        for config in [
+            "s2t", "s2tw", "s2twp", "s2hk", "t2s", "t2tw", "t2twp", "t2hk", "tw2s", "tw2sp",
+            "tw2t", "tw2tp", "hk2s", "hk2t", "t2jp", "jp2t",
+        ] {
+            let analysis = opencc.analyze(input, config);
+            let output: String = analysis.iter().map(|t| t.output.as_str()).collect();
+            assert_eq!(output, opencc.convert(input, config, false), "{}", config);
+        }
+        let analysis = opencc.analyze("龙", "s2t");
+        let matched = analysis[0].matched.as_ref().unwrap();
+        assert_eq!(matched.dict_name, "st_characters");
+        assert_eq!(matched.value, "龍");
+        assert!(opencc.analyze(input, "unknown").is_empty());
+    }
+
+    #[test]
+    fn load_decisions_test() {
+        let filename = std::env::temp_dir().join("opencc_jieba_decisions_test.txt");
+        fs::write(&filename, "里面\t裏面\n\n苹果电脑\tMac 電腦\n").unwrap();
+        let mut opencc = OpenCC::new();
+        opencc.load_decisions(&filename, "s2tw").unwrap();
+        // tw_variants would turn 裏 into 裡, the decision must win
+        let actual_output = opencc.s2tw("房间里面", false);
+        assert_eq!(actual_output, "房間裏面");
+        // Conversions may contain spaces
+        assert_eq!(opencc.s2tw("苹果电脑", false), "Mac 電腦");
+        // Decisions only apply to the config they were made for
+        assert_eq!(opencc.t2s("里面", false), "里面");
+        assert_eq!(opencc.s2t("苹果电脑", false), "蘋果電腦");
+        assert_eq!(opencc.lookup("里面", "t2s").len(), 0);
+        assert!(opencc.load_decisions(&filename, "no_such_config").is_err());
+
+        fs::write(&filename, "里面 裏面\n").unwrap();
+        let err = opencc.load_decisions(&filename, "s2tw").unwrap_err();
+        fs::remove_file(&filename).unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn coverage_test() {
+        let input = "龙马精神！龙马精神！";
+        let opencc = OpenCC::new();
+        let report = opencc.coverage(input, "s2t", 5);
+        assert_eq!(report.total_tokens, 2);
+        assert_eq!(
+            report.dictionary_hits + report.char_fallbacks,
+            report.total_tokens
+        );
+        assert_eq!(report.top_unmatched, vec![("龙马精神".to_string(), 2)]);
+    }
+
     #[test]
     fn test_jieba_cut() {
         let input = "「數大」便是美，碧綠的山坡前幾千隻綿羊，挨成一片的雪絨，是美；";
@@ -69,6 +162,176 @@ mod tests {
         assert_eq!(actual_output, expected_output);
     }
 
+    #[test]
+    #[cfg(feature = "keywords")]
+    fn keyword_extract_tfidf_test() {
+        let input = "今天纽约的天气真好啊，京华大酒店的张尧经理吃了一只北京烤鸭。";
+        let opencc = OpenCC::new();
+        let all_keywords = opencc.keyword_extract_tfidf(input, 10, &[]);
+        assert!(!all_keywords.is_empty());
+        let place_keywords = opencc.keyword_extract_tfidf(input, 10, &["ns"]);
+        assert!(place_keywords.contains(&"纽约".to_string()));
+        assert!(place_keywords.len() < all_keywords.len());
+    }
+
+    #[test]
+    #[cfg(feature = "keywords")]
+    fn keyword_spans_tfidf_test() {
+        let input = "北京烤鸭好吃，我爱北京烤鸭。";
+        let opencc = OpenCC::new();
+        let spans = opencc.keyword_spans_tfidf(input, 3, &[]);
+        assert!(!spans.is_empty());
+        for span in &spans {
+            assert!(!span.ranges.is_empty());
+            for range in &span.ranges {
+                assert_eq!(&input[range.clone()], span.keyword);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "keywords")]
+    fn load_idf_dict_test() {
+        let input = "生化学不是光化学的，光化学也不是生化学。";
+        let mut opencc = OpenCC::new();
+        let mut idf_dict = "生化学 13.900677652\n光化学 99.123456789\n".as_bytes();
+        opencc.load_idf_dict(&mut idf_dict).unwrap();
+        let keywords = opencc.keyword_extract_tfidf(input, 1, &[]);
+        assert_eq!(keywords, vec!["光化学".to_string()]);
+
+        for idf_dict in ["", "\n生化学\n光化学 high\n"] {
+            let err = opencc.load_idf_dict(&mut idf_dict.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        }
+        // The previous table stays in place after a failed load
+        let keywords = opencc.keyword_extract_tfidf(input, 1, &[]);
+        assert_eq!(keywords, vec!["光化学".to_string()]);
+    }
+
+    #[test]
+    #[cfg(feature = "keywords")]
+    fn keyword_weight_textrank_test() {
+        let input = "今天纽约的天气真好啊，京华大酒店的张尧经理吃了一只北京烤鸭。";
+        let opencc = OpenCC::new();
+        let keywords = opencc.keyword_weight_textrank(input, 5, &["n", "ns", "vn"]);
+        assert!(!keywords.is_empty());
+        assert!(keywords.windows(2).all(|w| w[0].weight >= w[1].weight));
+    }
+
+    #[test]
+    fn summarize_test() {
+        let input =
+            "北京烤鸭是北京的名菜。北京烤鸭皮脆肉嫩。今天下雨了。很多游客到北京品尝北京烤鸭。";
+        let opencc = OpenCC::new();
+        let summary = opencc.summarize(input, 2);
+        assert_eq!(summary.len(), 2);
+        assert!(!summary.contains(&"今天下雨了。".to_string()));
+        let first = input.find(summary[0].as_str()).unwrap();
+        let second = input.find(summary[1].as_str()).unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    #[cfg(feature = "arrow")]
+    fn convert_record_batch_test() {
+        use arrow_array::{Array, Int32Array, RecordBatch, StringArray};
+        use std::sync::Arc;
+        let opencc = OpenCC::new();
+        let batch = RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(Int32Array::from(vec![1, 2])) as _),
+            (
+                "text",
+                Arc::new(StringArray::from(vec![Some("龙马精神"), None])) as _,
+            ),
+        ])
+        .unwrap();
+
+        let converted = opencc
+            .convert_record_batch(&batch, "text", "s2t", false)
+            .unwrap();
+        let text = converted
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(text.value(0), "龍馬精神");
+        assert!(text.is_null(1));
+        assert!(opencc
+            .convert_record_batch(&batch, "id", "s2t", false)
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "collation")]
+    fn sort_collated_test() {
+        use opencc_jieba_rs::{sort_collated, Collation};
+        let mut words = vec!["中国", "北京", "安徽"];
+        sort_collated(&mut words, Collation::Pinyin);
+        assert_eq!(words, vec!["安徽", "北京", "中国"]);
+    }
+
+    #[test]
+    fn jieba_cut_for_search_test() {
+        let input = "南京市长江大桥";
+        let opencc = OpenCC::new();
+        let tokens = opencc.jieba_cut_for_search(input, true);
+        assert!(tokens.len() >= opencc.jieba_cut(input, true).len());
+        assert!(tokens.contains(&"南京".to_string()));
+    }
+
+    #[test]
+    fn add_word_test() {
+        let input = "我喜欢龙马精神饮料";
+        let mut opencc = OpenCC::new();
+        assert!(!opencc
+            .jieba_cut(input, false)
+            .contains(&"龙马精神饮料".to_string()));
+        let freq = opencc.suggest_freq("龙马精神饮料");
+        opencc.add_word("龙马精神饮料", Some(freq), Some("nz"));
+        assert!(opencc
+            .jieba_cut(input, false)
+            .contains(&"龙马精神饮料".to_string()));
+    }
+
+    #[test]
+    fn with_jieba_dict_test() {
+        let mut jieba_dict = "龙马精神饮料 100 nz\n我 100 r\n喜欢 100 v\n".as_bytes();
+        let opencc = OpenCC::with_jieba_dict(&mut jieba_dict).unwrap();
+        let actual_output = opencc.jieba_cut("我喜欢龙马精神饮料", false);
+        assert_eq!(actual_output, vec!["我", "喜欢", "龙马精神饮料"]);
+        assert_eq!(opencc.s2t("龙马精神饮料", false), "龍馬精神飲料");
+
+        let mut invalid_dict = "龙马 abc\n".as_bytes();
+        assert!(OpenCC::with_jieba_dict(&mut invalid_dict).is_err());
+    }
+
+    #[test]
+    fn jieba_cut_filtered_test() {
+        let input = "我们在北京吃烤鸭";
+        let opencc = OpenCC::new();
+        let stop_words: HashSet<String> = ["我们", "在"].iter().map(|s| s.to_string()).collect();
+        let actual_output = opencc.jieba_cut_filtered(input, true, &stop_words);
+        assert_eq!(actual_output, vec!["北京", "吃", "烤鸭"]);
+    }
+
+    #[test]
+    #[cfg(feature = "stop-words")]
+    fn default_stop_words_test() {
+        let stop_words = opencc_jieba_rs::default_stop_words();
+        assert!(stop_words.contains("的"));
+        assert!(stop_words.contains("這個"));
+    }
+
+    #[test]
+    fn jieba_tag_test() {
+        let input = "我们在北京吃烤鸭";
+        let opencc = OpenCC::new();
+        let tags = opencc.jieba_tag(input, true);
+        let words: Vec<String> = tags.iter().map(|(word, _)| word.clone()).collect();
+        assert_eq!(words, opencc.jieba_cut(input, true));
+        assert!(tags.contains(&("北京".to_string(), "ns".to_string())));
+    }
+
     #[test]
     fn s2t_punct_test() {
         let input = "你好，世界！“龙马精神”！";
@@ -78,6 +341,179 @@ mod tests {
         assert_eq!(actual_output, expected_output);
     }
 
+    #[test]
+    fn convert_and_truncate_test() {
+        let opencc = OpenCC::new();
+        let input = "意大利罗浮宫里收藏的";
+        assert_eq!(
+            opencc.convert_and_truncate(input, "s2twp", 100),
+            opencc.s2twp(input, false)
+        );
+        // Budget 13 columns + ellipsis: whole tokens only
+        assert_eq!(
+            opencc.convert_and_truncate(input, "s2twp", 14),
+            "義大利羅浮宮…"
+        );
+        // Single oversize token falls back to grapheme cut
+        assert_eq!(opencc.convert_and_truncate("abcdefgh", "s2t", 5), "abcd…");
+        assert_eq!(opencc.convert_and_truncate(input, "s2twp", 0), "");
+    }
+
+    #[test]
+    fn convert_with_alternatives_test() {
+        let dictionary = dictionary_lib::Dictionary::new().with_alternatives();
+        assert_eq!(
+            dictionary.get_all("st_characters", "干"),
+            vec!["幹", "乾", "干"]
+        );
+        assert_eq!(dictionary.get_all("st_characters", "龙"), vec!["龍"]);
+        assert!(dictionary.get_all("st_characters", "A").is_empty());
+
+        // The table's current value always comes first
+        let mut edited = dictionary_lib::Dictionary::new().with_alternatives();
+        edited
+            .st_characters
+            .insert("干".to_string(), "乾".to_string());
+        assert_eq!(
+            edited.get_all("st_characters", "干"),
+            vec!["乾", "幹", "干"]
+        );
+        // Patched and merged keys drop the stale alternatives
+        edited
+            .apply_patch("[st_characters]\n+干\t干\n-发\n".as_bytes())
+            .unwrap();
+        assert_eq!(edited.get_all("st_characters", "干"), vec!["干"]);
+        assert!(edited.get_all("st_characters", "发").is_empty());
+        let overlay = dictionary_lib::Dictionary {
+            st_characters: dictionary_lib::Dictionary::load_dictionary_from_str("干\t幹\n")
+                .unwrap(),
+            ..Default::default()
+        };
+        edited
+            .merge(&overlay, dictionary_lib::MergeStrategy::Override)
+            .unwrap();
+        assert_eq!(edited.get_all("st_characters", "干"), vec!["幹"]);
+
+        let opencc = OpenCC::with_dictionary(dictionary);
+        let input = "干了这杯";
+        let tokens = opencc.convert_with_alternatives(input, "s2t");
+        let first: String = tokens.iter().map(|t| t.candidates[0].as_str()).collect();
+        assert_eq!(first, opencc.s2t(input, false));
+        let gan = tokens.iter().find(|t| t.token == "干").unwrap();
+        assert_eq!(gan.candidates, vec!["幹", "乾", "干"]);
+    }
+
+    #[test]
+    fn lookup_test() {
+        let mut opencc = OpenCC::new();
+        let hits = opencc.lookup("意大利", "s2twp");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].round, 1);
+        assert_eq!(hits[0].dict_name, "tw_phrases");
+        assert_eq!(hits[0].value, "義大利");
+
+        let hits = opencc.lookup("龙", "s2t");
+        assert_eq!(hits[0].dict_name, "st_characters");
+        assert!(opencc.lookup("龙", "no_such_config").is_empty());
+
+        opencc.add_user_phrases(
+            "s2twp",
+            HashMap::from([("意大利".to_string(), "意大利".to_string())]),
+        );
+        let hits = opencc.lookup("意大利", "s2twp");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].dict_name, "user_phrases");
+        assert!(opencc.lookup("意大利", "s2tw").is_empty());
+    }
+
+    #[test]
+    fn convert_with_hooks_test() {
+        let opencc = OpenCC::new();
+        let input = "意大利罗浮宫里收藏的“蒙娜丽莎的微笑”画像是旷世之作。";
+        let mut rounds_seen = HashSet::new();
+        let mut hooks = RoundHooks::new().on_round_start(|round, _| {
+            rounds_seen.insert(round);
+        });
+        assert_eq!(
+            opencc.convert_with_hooks(input, "s2twp", true, &mut hooks),
+            opencc.s2twp(input, true)
+        );
+        drop(hooks);
+        assert_eq!(rounds_seen, HashSet::from([0, 1, 2]));
+
+        let mut trace = Vec::new();
+        let mut hooks = RoundHooks::new().on_round_start(|round, text| {
+            trace.push((round, text.clone()));
+        });
+        assert_eq!(
+            opencc.convert_with_hooks("意大利", "s2twp", false, &mut hooks),
+            "義大利"
+        );
+        drop(hooks);
+        assert_eq!(
+            trace,
+            vec![
+                (0, "意大利".to_string()),
+                (1, "意大利".to_string()),
+                (2, "義大利".to_string())
+            ]
+        );
+
+        // Normalize between tw_phrases and tw_variants
+        let mut hooks = RoundHooks::new().on_round_end(|round, text| {
+            if round == 1 {
+                *text = text.replace("義", "意");
+            }
+        });
+        assert_eq!(
+            opencc.convert_with_hooks("意大利", "s2twp", false, &mut hooks),
+            "意大利"
+        );
+
+        // Once per round with the whole text, so edits can span token boundaries
+        let mut calls = Vec::new();
+        let mut hooks = RoundHooks::new().on_round_start(|round, text| {
+            calls.push((round, text.clone()));
+            if round == 0 {
+                *text = text.replace("罗浮宫里收藏", "罗浮宫收藏");
+            }
+        });
+        let output = opencc.convert_with_hooks(input, "s2twp", false, &mut hooks);
+        drop(hooks);
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0], (0, input.to_string()));
+        assert_eq!(
+            output,
+            opencc.s2twp(&input.replace("里收藏", "收藏"), false)
+        );
+    }
+
+    #[test]
+    fn convert_into_test() {
+        let input = "你好，世界！“龙马精神”！";
+        let opencc = OpenCC::new();
+        let expected_output = opencc.convert(input, "s2twp", true);
+
+        let mut string_sink = String::new();
+        opencc
+            .convert_into(input, "s2twp", true, &mut string_sink)
+            .unwrap();
+        assert_eq!(string_sink, expected_output);
+
+        let mut write_sink = WriteSink::new(Vec::new());
+        opencc
+            .convert_into(input, "s2twp", true, &mut write_sink)
+            .unwrap();
+        assert_eq!(write_sink.into_inner(), expected_output.as_bytes());
+
+        let (mut sender, receiver) = mpsc::channel();
+        opencc
+            .convert_into(input, "s2twp", true, &mut sender)
+            .unwrap();
+        drop(sender);
+        assert_eq!(receiver.iter().collect::<String>(), expected_output);
+    }
+
     #[test]
     fn format_thousand_test() {
         let input = 1234567890;
@@ -86,6 +522,14 @@ mod tests {
         assert_eq!(actual_output, expected_output);
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_root_helpers_test() {
+        // Old call sites must keep compiling until the shims are removed
+        assert_eq!(opencc_jieba_rs::format_thousand(1234), "1,234");
+        assert_eq!(opencc_jieba_rs::find_max_utf8_length("你好", 4), 3);
+    }
+
     #[test]
     fn test_zho_check() {
         let input = "你好，世界！龙马精神！";
@@ -95,6 +539,298 @@ mod tests {
         assert_eq!(actual_output, expected_output);
     }
 
+    #[test]
+    fn char_table_test() {
+        // Struct literal: char tables are derived when OpenCC takes the dictionary
+        let dictionary = dictionary_lib::Dictionary {
+            st_characters: dictionary_lib::Dictionary::load_dictionary_from_str("龙\t龍\n")
+                .unwrap(),
+            ts_characters: dictionary_lib::Dictionary::load_dictionary_from_str("龍\t龙\n")
+                .unwrap(),
+            ..Default::default()
+        };
+        let mut opencc = OpenCC::with_dictionary(dictionary);
+        assert_eq!(opencc.convert_chars("龙", "s2t"), "龍");
+        assert_eq!(opencc.zho_check("龍"), 1);
+        assert_eq!(opencc.zho_check("龙"), 2);
+
+        // Public fields edited after loading
+        let mut dictionary = dictionary_lib::Dictionary::new();
+        dictionary
+            .st_characters
+            .insert("龙".to_string(), "竜".to_string());
+        opencc.set_dictionary(dictionary);
+        assert_eq!(opencc.convert_chars("龙", "s2t"), "竜");
+        assert_eq!(opencc.convert_chars("龙", "s2tw"), "竜");
+    }
+
+    #[test]
+    fn from_path_test() {
+        let dictionary = dictionary_lib::Dictionary::from_path("src/dictionary_lib/dicts").unwrap();
+        let embedded = dictionary_lib::Dictionary::new();
+        assert_eq!(dictionary.st_phrases.len(), embedded.st_phrases.len());
+        assert_eq!(dictionary.st_characters.len(), embedded.st_characters.len());
+        assert_eq!(
+            dictionary.get_all("st_characters", "干"),
+            vec!["幹", "乾", "干"]
+        );
+        assert!(dictionary_lib::Dictionary::from_path("no/such/dir").is_err());
+
+        let opencc = OpenCC::with_dictionary(dictionary);
+        assert_eq!(opencc.s2t("龙马精神", false), "龍馬精神");
+    }
+
+    #[test]
+    fn export_to_dir_test() {
+        let dictionary = dictionary_lib::Dictionary::new();
+        let dir = std::env::temp_dir().join("opencc_jieba_export_test");
+        dictionary.export_to_dir(&dir).unwrap();
+        let reloaded = dictionary_lib::Dictionary::from_path(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        for ((name, table), (_, reloaded_table)) in
+            dictionary.tables().iter().zip(reloaded.tables().iter())
+        {
+            assert_eq!(table, reloaded_table, "{}", name);
+        }
+
+        // Alternatives survive export -> from_path and JSON round trips
+        let dictionary = dictionary_lib::Dictionary::new().with_alternatives();
+        dictionary.export_to_dir(&dir).unwrap();
+        let reloaded = dictionary_lib::Dictionary::from_path(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            reloaded.get_all("st_characters", "干"),
+            vec!["幹", "乾", "干"]
+        );
+
+        let filename = std::env::temp_dir().join("opencc_jieba_alternatives_test.json");
+        let filename = filename.to_string_lossy();
+        reloaded.serialize_to_json(&filename).unwrap();
+        let from_json = dictionary_lib::Dictionary::from_json_file(&filename).unwrap();
+        fs::remove_file(filename.as_ref()).unwrap();
+        assert_eq!(
+            from_json.get_all("st_characters", "干"),
+            vec!["幹", "乾", "干"]
+        );
+    }
+
+    #[test]
+    fn merge_test() {
+        use dictionary_lib::{Dictionary, MergeStrategy};
+        let overlay = Dictionary {
+            st_phrases: Dictionary::load_dictionary_from_str("一丝不挂\t一絲不挂\n").unwrap(),
+            st_characters: Dictionary::load_dictionary_from_str("丝\t糸\n").unwrap(),
+            ..Default::default()
+        };
+
+        let mut dictionary = Dictionary::new();
+        assert!(dictionary
+            .merge(&overlay, MergeStrategy::ErrorOnConflict)
+            .is_err());
+        assert_eq!(dictionary.st_phrases["一丝不挂"], "一絲不掛");
+
+        dictionary.merge(&overlay, MergeStrategy::Keep).unwrap();
+        assert_eq!(dictionary.st_phrases["一丝不挂"], "一絲不掛");
+
+        dictionary.merge(&overlay, MergeStrategy::Override).unwrap();
+        assert_eq!(dictionary.st_phrases["一丝不挂"], "一絲不挂");
+        let opencc = OpenCC::with_dictionary(dictionary);
+        assert_eq!(opencc.convert_chars("丝", "s2t"), "糸");
+    }
+
+    #[test]
+    fn serialize_sorted_test() {
+        let dictionary = dictionary_lib::Dictionary {
+            st_phrases: dictionary_lib::Dictionary::load_dictionary_from_str(
+                "龙马\t龍馬\n一丝不挂\t一絲不掛\n万里\t萬里\n",
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&dictionary).unwrap();
+        assert!(
+            json.contains(r#""st_phrases":{"一丝不挂":"一絲不掛","万里":"萬里","龙马":"龍馬"}"#)
+        );
+        assert_eq!(json, serde_json::to_string(&dictionary).unwrap());
+    }
+
+    #[test]
+    fn stats_test() {
+        let dictionary = dictionary_lib::Dictionary::new();
+        let stats = dictionary.stats();
+        assert_eq!(stats.len(), 16);
+        assert_eq!(stats[0].table, "st_characters");
+        assert_eq!(stats[0].entries, dictionary.st_characters.len());
+        for table_stats in &stats {
+            assert_eq!(
+                table_stats.key_len_histogram.values().sum::<usize>(),
+                table_stats.entries
+            );
+        }
+        assert!(stats[1].heap_bytes > stats[1].entries * 6);
+    }
+
+    #[test]
+    fn diff_test() {
+        use dictionary_lib::{Dictionary, MergeStrategy};
+        let old = Dictionary::new();
+        assert!(old.diff(&Dictionary::new()).is_empty());
+
+        let mut new = Dictionary::new();
+        let overlay = Dictionary {
+            st_phrases: Dictionary::load_dictionary_from_str("一丝不挂\t一絲不挂\n龙马\t龍馬\n")
+                .unwrap(),
+            ..Default::default()
+        };
+        new.merge(&overlay, MergeStrategy::Override).unwrap();
+        new.tw_variants.remove("啓");
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(diff[0].table, "st_phrases");
+        assert_eq!(
+            diff[0].added,
+            vec![("龙马".to_string(), "龍馬".to_string())]
+        );
+        assert_eq!(
+            diff[0].changed,
+            vec![(
+                "一丝不挂".to_string(),
+                "一絲不掛".to_string(),
+                "一絲不挂".to_string()
+            )]
+        );
+        assert_eq!(diff[1].table, "tw_variants");
+        assert_eq!(diff[1].removed.len(), 1);
+    }
+
+    #[test]
+    fn reload_dictionary_test() {
+        let mut opencc = OpenCC::new();
+        let dictionary = dictionary_lib::Dictionary {
+            st_characters: dictionary_lib::Dictionary::load_dictionary_from_str("龙\t竜\n")
+                .unwrap(),
+            ..Default::default()
+        };
+        let path = std::env::temp_dir().join("opencc_jieba_reload_test.json");
+        dictionary
+            .serialize_to_json(&path.to_string_lossy())
+            .unwrap();
+
+        assert!(opencc.reload_dictionary("no/such/dictionary.json").is_err());
+        assert_eq!(opencc.s2t("龙", false), "龍");
+        opencc.reload_dictionary(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(opencc.s2t("龙", false), "竜");
+        assert_eq!(opencc.jieba_cut("你好，世界！", true).len(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "watch")]
+    fn dictionary_watcher_test() {
+        use opencc_jieba_rs::watch::DictionaryWatcher;
+        use std::time::{Duration, Instant};
+        let dir = std::env::temp_dir().join("opencc_jieba_watch_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("dictionary.json");
+        dictionary_lib::Dictionary::default()
+            .serialize_to_json(&path.to_string_lossy())
+            .unwrap();
+
+        let mut opencc = OpenCC::new();
+        let watcher = DictionaryWatcher::new(&path).unwrap();
+        let dictionary = dictionary_lib::Dictionary {
+            st_characters: dictionary_lib::Dictionary::load_dictionary_from_str("龙\t竜\n")
+                .unwrap(),
+            ..Default::default()
+        };
+        dictionary
+            .serialize_to_json(&path.to_string_lossy())
+            .unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while opencc.s2t("龙", false) != "竜" && Instant::now() < deadline {
+            let _ = watcher.apply(&mut opencc);
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(opencc.s2t("龙", false), "竜");
+    }
+
+    #[test]
+    fn apply_patch_test() {
+        let patch = "# glossary fixes\n[st_phrases]\n+一丝不挂\t一絲不挂\n[tw_variants]\n-啓\n";
+        let mut dictionary = dictionary_lib::Dictionary::new();
+        dictionary.apply_patch(patch.as_bytes()).unwrap();
+        assert_eq!(dictionary.st_phrases["一丝不挂"], "一絲不挂");
+        assert!(!dictionary.tw_variants.contains_key("啓"));
+
+        let mut dictionary = dictionary_lib::Dictionary::new();
+        let bad_table = "[st_phrases]\n+一丝不挂\t一絲不挂\n[no_such_table]\n-啓\n";
+        assert!(dictionary.apply_patch(bad_table.as_bytes()).is_err());
+        // Nothing applied from a rejected patch
+        assert_eq!(dictionary.st_phrases["一丝不挂"], "一絲不掛");
+        assert!(dictionary.apply_patch("+龙\t竜\n".as_bytes()).is_err());
+        for bad_entry in ["+龙 竜", "+龙\t", "+\t竜", "-"] {
+            let patch = format!("[st_characters]\n{}\n", bad_entry);
+            let err = dictionary.apply_patch(patch.as_bytes()).unwrap_err();
+            assert!(err.to_string().contains("line 2"), "{}", bad_entry);
+        }
+
+        // Values may contain spaces
+        let patch = "[st_phrases]\n+苹果电脑\tMac 電腦\n";
+        dictionary.apply_patch(patch.as_bytes()).unwrap();
+        assert_eq!(dictionary.st_phrases["苹果电脑"], "Mac 電腦");
+
+        let path = std::env::temp_dir().join("opencc_jieba_patch_test.txt");
+        fs::write(&path, "[st_characters]\n+龙\t竜\n").unwrap();
+        let opencc = OpenCC::with_patch(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(opencc.convert_chars("龙", "s2t"), "竜");
+    }
+
+    #[test]
+    fn reversed_test() {
+        let table = dictionary_lib::Dictionary::load_dictionary_from_str(
+            "软件\t軟體\n内存\t記憶體\n存储器\t記憶體\n",
+        )
+        .unwrap();
+        let reversed = dictionary_lib::Dictionary::reversed(&table);
+        assert_eq!(reversed.table.len(), 2);
+        assert_eq!(reversed.table["軟體"], "软件");
+        assert_eq!(reversed.table["記憶體"], "内存");
+        assert_eq!(
+            reversed.ambiguous,
+            vec![(
+                "記憶體".to_string(),
+                vec!["内存".to_string(), "存储器".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn load_dictionary_from_str_test() {
+        let mut dictionary = dictionary_lib::Dictionary::new();
+        dictionary.tw_phrases =
+            dictionary_lib::Dictionary::load_dictionary_from_str("軟件\t軟體\n內存\t記憶體\n")
+                .unwrap();
+        assert_eq!(dictionary.tw_phrases.len(), 2);
+        let opencc = OpenCC::with_dictionary(dictionary);
+        assert_eq!(opencc.s2twp("内存", false), "記憶體");
+
+        let reader = "內存 記憶體 內存\n".as_bytes();
+        let table = dictionary_lib::Dictionary::load_dictionary_from_reader(reader).unwrap();
+        assert_eq!(table.get("內存").unwrap(), "記憶體");
+
+        let table =
+            dictionary_lib::Dictionary::load_dictionary_from_str("\n軟件\t軟體\n  \n").unwrap();
+        assert_eq!(table.len(), 1);
+        let err =
+            dictionary_lib::Dictionary::load_dictionary_from_str("軟件\t軟體\n內存\n").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
     #[test]
     #[ignore]
     // In case there are new update to dictionaries contents,
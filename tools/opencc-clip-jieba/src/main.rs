@@ -1,128 +1,306 @@
 extern crate copypasta;
 
 use std::env;
+use std::io::{self, IsTerminal};
+use std::thread;
+use std::time::Duration;
 
 use copypasta::ClipboardContext;
 use copypasta::ClipboardProvider;
-use opencc_jieba_rs::{find_max_utf8_length, format_thousand, OpenCC};
+use opencc_jieba_rs::utils::{find_max_utf8_length, format_thousand};
+use opencc_jieba_rs::OpenCC;
 
-fn main() {
-    let config_list = [
-        "s2t", "t2s", "s2tw", "tw2s", "s2twp", "tw2sp", "s2hk", "hk2s", "t2tw", "t2twp", "t2hk",
-        "tw2t", "tw2tp", "hk2t", "t2jp", "jp2t",
-    ];
-    const RED: &str = "\x1B[1;31m";
-    const GREEN: &str = "\x1B[1;32m";
-    const YELLOW: &str = "\x1B[1;33m";
-    const BLUE: &str = "\x1B[1;34m";
-    const RESET: &str = "\x1B[0m";
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+struct Messages {
+    title: &'static str,
+    usage: &'static str,
+    config: &'static str,
+    clipboard_input: &'static str,
+    converted_output: &'static str,
+    output_set: &'static str,
+    chars: &'static str,
+    error_set: &'static str,
+    no_text: &'static str,
+    watching: &'static str,
+}
+
+const MESSAGES_EN: Messages = Messages {
+    title: "Opencc-Clip-Jieba Zho Converter version 1.0.0 Copyright (c) 2024 Bryan Lai",
+    usage: "Usage: opencc-clip-jieba [s2t|t2s|s2tw|tw2s|s2twp|tw2sp|s2hk|hk2s|t2tw|t2twp|tw2t|tw2tp|t2hk|hk2t|jp2t|t2jp|auto|help] [punct] [--lang en|zh] [--color auto|always|never] [--watch]\n",
+    config: "Config",
+    clipboard_input: "Clipboard Input",
+    converted_output: "Converted Output",
+    output_set: "Output set to clipboard",
+    chars: "chars",
+    error_set: "Error set clipboard",
+    no_text: "No text in clipboard",
+    watching: "Watching clipboard, press Ctrl+C to stop.",
+};
+
+const MESSAGES_ZH: Messages = Messages {
+    title: "Opencc-Clip-Jieba 中文简繁转换器 版本 1.0.0 Copyright (c) 2024 Bryan Lai",
+    usage: "用法: opencc-clip-jieba [s2t|t2s|s2tw|tw2s|s2twp|tw2sp|s2hk|hk2s|t2tw|t2twp|tw2t|tw2tp|t2hk|hk2t|jp2t|t2jp|auto|help] [punct] [--lang en|zh] [--color auto|always|never] [--watch]\n",
+    config: "转换配置",
+    clipboard_input: "剪贴板输入",
+    converted_output: "转换输出",
+    output_set: "已输出至剪贴板",
+    chars: "字",
+    error_set: "写入剪贴板出错",
+    no_text: "剪贴板中没有文本",
+    watching: "正在监视剪贴板，按 Ctrl+C 结束。",
+};
+
+struct Palette {
+    red: &'static str,
+    green: &'static str,
+    yellow: &'static str,
+    blue: &'static str,
+    reset: &'static str,
+}
+
+const ANSI_COLORS: Palette = Palette {
+    red: "\x1B[1;31m",
+    green: "\x1B[1;32m",
+    yellow: "\x1B[1;33m",
+    blue: "\x1B[1;34m",
+    reset: "\x1B[0m",
+};
+
+const NO_COLORS: Palette = Palette {
+    red: "",
+    green: "",
+    yellow: "",
+    blue: "",
+    reset: "",
+};
+
+// --color always|never, auto (default): color only on a terminal and without NO_COLOR
+fn palette(color: Option<&str>) -> &'static Palette {
+    let enabled = match color {
+        Some("always") => true,
+        Some("never") => false,
+        _ => {
+            env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+                && io::stdout().is_terminal()
+        }
+    };
+    if enabled {
+        &ANSI_COLORS
+    } else {
+        &NO_COLORS
+    }
+}
+
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    match args.iter().position(|arg| arg == name) {
+        Some(pos) => {
+            args.remove(pos);
+            true
+        }
+        None => false,
+    }
+}
+
+// Remove "--name value" from args, returning the value
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let pos = args.iter().position(|arg| arg == name)?;
+    let value = (pos + 1 < args.len()).then(|| args.remove(pos + 1));
+    args.remove(pos);
+    value
+}
 
+// --lang en|zh, otherwise Chinese when LC_ALL / LANG is zh_*
+fn messages(lang: Option<&str>) -> &'static Messages {
+    let lang = match lang {
+        Some(lang) => lang.to_string(),
+        // An empty LC_ALL means unset, so fall through to LANG
+        None => env::var("LC_ALL")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .or_else(|| env::var("LANG").ok())
+            .unwrap_or_default(),
+    };
+    if lang.to_lowercase().starts_with("zh") {
+        &MESSAGES_ZH
+    } else {
+        &MESSAGES_EN
+    }
+}
+
+const CONFIG_LIST: [&str; 16] = [
+    "s2t", "t2s", "s2tw", "tw2s", "s2twp", "tw2sp", "s2hk", "hk2s", "t2tw", "t2twp", "t2hk",
+    "tw2t", "tw2tp", "hk2t", "t2jp", "jp2t",
+];
+
+fn main() {
     let mut config;
     let mut punct = false;
-    let args: Vec<String> = env::args().collect();
+    let mut args: Vec<String> = env::args().collect();
+    let msg = messages(take_option(&mut args, "--lang").as_deref());
+    let color = palette(take_option(&mut args, "--color").as_deref());
+    let watch = take_flag(&mut args, "--watch");
 
     if args.len() > 1 {
         config = args[1].clone();
         if config == "help" {
-            println!("Opencc-Clip-Jieba Zho Converter version 1.0.0 Copyright (c) 2024 Bryan Lai");
-            println!("Usage: opencc-clip-jieba [s2t|t2s|s2tw|tw2s|s2twp|tw2sp|s2hk|hk2s|t2tw|tw2t|t2twp|tw2t|tw2tp|t2hk|hk2t|jp2t|t2jp|auto|help] [punct]\n");
+            println!("{}", msg.title);
+            println!("{}", msg.usage);
             return;
         }
-        if !config_list.contains(&config.as_str()) {
+        if !CONFIG_LIST.contains(&config.as_str()) {
             config = "auto".to_string()
         }
-        if args.len() > 2 {
-            if args[2] == "punct" {
-                punct = true
-            }
+        if args.len() > 2 && args[2] == "punct" {
+            punct = true
         }
     } else {
         config = "auto".to_string()
     }
     // Create a new clipboard context
     let mut ctx: ClipboardContext = ClipboardContext::new().unwrap();
+    let opencc = OpenCC::new();
+    println!("{}", msg.title);
+
+    if watch {
+        println!("{}{}{}", color.blue, msg.watching, color.reset);
+        // Start from the current contents, only text copied from now on is converted
+        let mut last_seen = ctx.get_contents().ok();
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+            let contents = match ctx.get_contents() {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+            // Our own output is what we last saw, so it is never converted again
+            if last_seen.as_deref() == Some(contents.as_str()) {
+                continue;
+            }
+            let output = convert_clipboard(
+                &mut ctx, &opencc, &contents, &config, punct, true, msg, color,
+            );
+            last_seen = Some(output.unwrap_or(contents));
+        }
+    }
+
     // Attempt to read text from the clipboard
     match ctx.get_contents() {
         Ok(contents) => {
-            // If successful, print the text to the console
-            let display_input;
-            let display_output;
-            let display_input_code;
-            let display_output_code;
-            let etc;
-            let output;
-            let opencc = OpenCC::new();
-            let input_code = opencc.zho_check(contents.as_str());
-
-            if config == "auto" {
-                match input_code {
-                    1 => config = "t2s".to_string(),
-                    2 => config = "s2t".to_string(),
-                    _ => config = "none".to_string(),
-                }
-            }
+            convert_clipboard(
+                &mut ctx, &opencc, &contents, &config, punct, false, msg, color,
+            );
+        }
+        Err(err) => {
+            // If an error occurs, print the error message
+            eprintln!("{}{}: {}{}", color.red, msg.no_text, err, color.reset)
+        }
+    }
+}
 
-            let input_length = contents.chars().collect::<Vec<_>>().len();
-
-            if input_code == 0 || config == "t2jp" || config == "jp2t" {
-                display_input_code = "Non-zho 其它";
-                display_output_code = "Non-zho 其它";
-            } else if config.starts_with('s') {
-                display_input_code = "Simplified Chinese 简体";
-                display_output_code = "Traditional Chinese 繁体";
-            } else if config.ends_with('s') || config.ends_with('p') {
-                display_input_code = "Traditional Chinese 繁体";
-                display_output_code = "Simplified Chinese 简体";
-            } else {
-                display_input_code = "Traditional Chinese 繁体";
-                display_output_code = "Traditional Chinese 繁体";
-            }
+// Convert and print, then put the output on the clipboard. Returns the output if set.
+// In watch mode non-Chinese text and text that would not change are left alone.
+#[allow(clippy::too_many_arguments)]
+fn convert_clipboard(
+    ctx: &mut ClipboardContext,
+    opencc: &OpenCC,
+    contents: &str,
+    config: &str,
+    punct: bool,
+    watch: bool,
+    msg: &Messages,
+    color: &Palette,
+) -> Option<String> {
+    let display_input_code;
+    let display_output_code;
+    let input_code = opencc.zho_check(contents);
 
-            if config_list.contains(&config.as_str()) {
-                output = opencc.convert(&contents, &config, punct);
-            } else {
-                output = contents.clone();
-            }
+    let config = if config == "auto" {
+        match input_code {
+            1 => "t2s",
+            2 => "s2t",
+            _ => "none",
+        }
+    } else {
+        config
+    };
 
-            if contents.len() > 600 {
-                let max_utf8_length = find_max_utf8_length(&contents, 600);
-                display_input = &contents[..max_utf8_length];
-                etc = "...";
-                display_output = &output[..max_utf8_length];
-            } else {
-                display_input = &contents;
-                etc = "";
-                display_output = &output;
-            }
+    let input_length = contents.chars().count();
 
-            println!("Opencc-Clip-Jieba Zho Converter version 1.0.0 Copyright (c) 2024 Bryan Lai");
-            println!("Config: {}{}, {}{}", BLUE, config, punct, RESET);
-            println!(
-                "{}Clipboard Input ({}):{}\n{}{}{}{}\n",
-                GREEN, &display_input_code, RESET, YELLOW, &display_input, etc, RESET
-            );
+    if input_code == 0 || config == "t2jp" || config == "jp2t" {
+        display_input_code = "Non-zho 其它";
+        display_output_code = "Non-zho 其它";
+    } else if config.starts_with('s') {
+        display_input_code = "Simplified Chinese 简体";
+        display_output_code = "Traditional Chinese 繁体";
+    } else if config.ends_with('s') || config.ends_with('p') {
+        display_input_code = "Traditional Chinese 繁体";
+        display_output_code = "Simplified Chinese 简体";
+    } else {
+        display_input_code = "Traditional Chinese 繁体";
+        display_output_code = "Traditional Chinese 繁体";
+    }
+
+    let output = if CONFIG_LIST.contains(&config) {
+        opencc.convert(contents, config, punct)
+    } else {
+        contents.to_string()
+    };
+    if watch && (input_code == 0 || output == contents) {
+        return None;
+    }
+
+    let (display_input, display_output, etc) = if contents.len() > 600 {
+        (
+            &contents[..find_max_utf8_length(contents, 600)],
+            &output[..find_max_utf8_length(&output, 600)],
+            "...",
+        )
+    } else {
+        (contents, output.as_str(), "")
+    };
+
+    println!(
+        "{}: {}{}, {}{}",
+        msg.config, color.blue, config, punct, color.reset
+    );
+    println!(
+        "{}{} ({}):{}\n{}{}{}{}\n",
+        color.green,
+        msg.clipboard_input,
+        &display_input_code,
+        color.reset,
+        color.yellow,
+        &display_input,
+        etc,
+        color.reset
+    );
+    println!(
+        "{}{} ({}):{}\n{}{}{}{}",
+        color.green,
+        msg.converted_output,
+        &display_output_code,
+        color.reset,
+        color.yellow,
+        &display_output,
+        etc,
+        color.reset
+    );
+
+    match ctx.set_contents(output.clone()) {
+        Ok(..) => {
             println!(
-                "{}Converted Output ({}):{}\n{}{}{}{}",
-                GREEN, &display_output_code, RESET, YELLOW, &display_output, etc, RESET
+                "{}({}: {} {}.){}",
+                color.blue,
+                msg.output_set,
+                format_thousand(input_length as i32),
+                msg.chars,
+                color.reset
             );
-
-            match ctx.set_contents(output) {
-                Ok(..) => {
-                    println!(
-                        "{}(Output set to clipboard: {} chars.){}",
-                        BLUE,
-                        format_thousand(input_length as i32),
-                        RESET
-                    )
-                }
-                Err(err) => {
-                    eprintln!("{}Error set clipboard: {}{}", RED, err, RESET)
-                }
-            }
+            Some(output)
         }
         Err(err) => {
-            // If an error occurs, print the error message
-            eprintln!("{}No text in clipboard: {}{}", RED, err, RESET)
+            eprintln!("{}{}: {}{}", color.red, msg.error_set, err, color.reset);
+            None
         }
     }
 }